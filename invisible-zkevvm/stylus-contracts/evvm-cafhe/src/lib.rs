@@ -20,20 +20,39 @@ extern crate alloc;
 
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use alloc::format;
 
 use stylus_sdk::prelude::*;
-use stylus_sdk::alloy_primitives::{Address, U256};
-use stylus_sdk::storage::{StorageMap, StorageAddress, StorageBool};
+use stylus_sdk::alloy_primitives::{keccak256, Address, B256, U256};
+use stylus_sdk::storage::{StorageMap, StorageAddress, StorageBool, StorageU256};
 use stylus_sdk::call::Call;
+use stylus_sdk::types::AddressVM;
 use stylus_sdk::msg;
 use stylus_sdk::contract;
+use stylus_sdk::evm;
+use stylus_sdk::alloy_sol_types;
+use alloy_sol_types::sol;
 
 // Import FHE middleware
 use fhe_stylus::prelude::*;
+use fhe_stylus::config::{FHEVMConfig, StoredFHEVMConfig};
+use fhe_stylus::gateway::{
+    complete_request, require_pending, DecryptionError, PendingDecryptions,
+};
+#[cfg(feature = "reentrant")]
+use fhe_stylus::guard::require_not_entered;
+use fhe_stylus::guard::{
+    require_contract, require_not_initialized, require_owner, require_staker, OwnershipTransfer,
+};
+use fhe_stylus::nonce::{
+    are_nonces_unused, consume_nonce, is_nonce_used, mark_nonce_range, nonce_domain, NonceError,
+};
+use fhe_stylus::order::{check_order_total, check_quantity, OrderError};
+use fhe_stylus::revert::{call_failed, classify_pay_revert, CallFailure, PayRejection};
 use fhe_stylus::interfaces::IEVVMCore;
 
 // Unit tests - only compile for WASM target
+// Pure checks used by this contract are tested natively in fhe-stylus
+// (order, nonce, guard, gateway and revert modules)
 #[cfg(all(test, target_arch = "wasm32"))]
 mod tests {
     use super::*;
@@ -47,18 +66,67 @@ mod tests {
 
 // Panic handler for no_std - only for WASM target in production, not for tests
 // (global allocator provided by stylus-sdk)
-#[cfg(all(target_arch = "wasm32", not(feature = "std")))]
+// Enable the `panic-revert` feature to revert with the panic message instead
+#[cfg(all(target_arch = "wasm32", not(feature = "std"), not(feature = "panic-revert")))]
 #[panic_handler]
 fn panic(_info: &core::panic::PanicInfo) -> ! {
     loop {}
 }
 
+// The fhe-stylus handler would clash with std's own `panic_impl`
+#[cfg(all(target_arch = "wasm32", feature = "std", feature = "panic-revert"))]
+compile_error!("`panic-revert` requires a no_std build: use --no-default-features");
+
 /// Custom errors for EVVMCafhe
 mod errors {
     pub const INVALID_SIGNATURE: &[u8] = b"Invalid signature";
     pub const NONCE_ALREADY_USED: &[u8] = b"Nonce already used";
     pub const UNAUTHORIZED: &[u8] = b"Unauthorized";
     pub const PAYMENT_FAILED: &[u8] = b"Payment failed";
+    pub const PAYMENT_NONCE_REJECTED: &[u8] = b"Payment failed: nonce rejected";
+    pub const PAYMENT_SIGNATURE_REJECTED: &[u8] = b"Payment failed: signature rejected";
+    pub const PAYMENT_TOKEN_NOT_WHITELISTED: &[u8] = b"Payment failed: token not whitelisted";
+    pub const INVALID_NONCE_RANGE: &[u8] = b"Invalid nonce range";
+    pub const NOT_STAKER: &[u8] = b"Caller is not a staker";
+    pub const STAKER_CHECK_FAILED: &[u8] = b"Staker check failed";
+    pub const NOT_ON_MENU: &[u8] = b"Coffee not on menu";
+    pub const PRICE_MISMATCH: &[u8] = b"Price mismatch";
+    pub const PRICE_OVERFLOW: &[u8] = b"Price overflow";
+    pub const NOT_PENDING_OWNER: &[u8] = b"Not pending owner";
+    #[cfg(feature = "reentrant")]
+    pub const REENTRANT_CALL: &[u8] = b"Reentrant call";
+    pub const DECRYPTION_REQUEST_FAILED: &[u8] = b"Decryption request failed";
+    pub const UNKNOWN_DECRYPTION_REQUEST: &[u8] = b"Unknown decryption request";
+    pub const DECRYPTION_NOT_READY: &[u8] = b"Decryption not ready";
+    pub const ALREADY_INITIALIZED: &[u8] = b"Already initialized";
+    pub const QUANTITY_TOO_LARGE: &[u8] = b"Quantity too large";
+    pub const INVALID_EVVM_CORE: &[u8] = b"Invalid EVVM core";
+    pub const BALANCE_READ_FAILED: &[u8] = b"Balance read failed";
+    pub const INVALID_FHEVM_CONFIG: &[u8] = b"Invalid FHEVM config";
+}
+
+/// `map_err` adapter for `EVVMCore.pay`
+///
+/// Known revert reasons become specific `Payment failed: ...` errors; anything
+/// else falls back to `Payment failed: revert <raw data>`.
+fn pay_failed(err: stylus_sdk::call::Error) -> Vec<u8> {
+    match CallFailure::from(err) {
+        CallFailure::Revert(data) => match classify_pay_revert(&data) {
+            Some(PayRejection::Nonce) => errors::PAYMENT_NONCE_REJECTED.to_vec(),
+            Some(PayRejection::Signature) => errors::PAYMENT_SIGNATURE_REJECTED.to_vec(),
+            Some(PayRejection::TokenNotWhitelisted) => errors::PAYMENT_TOKEN_NOT_WHITELISTED.to_vec(),
+            None => CallFailure::Revert(data).encode(errors::PAYMENT_FAILED),
+        },
+        failure => failure.encode(errors::PAYMENT_FAILED),
+    }
+}
+
+sol! {
+    /// Emitted when the owner proposes a new shop owner
+    event OwnershipTransferStarted(address indexed previousOwner, address indexed newOwner);
+
+    /// Emitted when the proposed owner accepts ownership
+    event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
 }
 
 /// Constant representing ETH in the EVVM virtual blockchain
@@ -69,19 +137,88 @@ const PRINCIPAL_TOKEN_ADDRESS: Address = Address::new([
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1
 ]);
 
+/// Nonce domain of `orderCoffee` in `check_async_nonce`
+///
+/// Nonces are namespaced by function so a nonce signed for `orderCoffee`
+/// cannot be consumed by, or replayed against, another signed function
+/// added later. Nonces stored by older deployments under their raw value
+/// are still rejected; see the migration note in `fhe_stylus::nonce`.
+fn order_nonce_domain() -> [u8; 4] {
+    nonce_domain("orderCoffee")
+}
+
+/// `map_err` adapter for the nonce range helpers
+fn nonce_rejected(err: NonceError) -> Vec<u8> {
+    match err {
+        NonceError::InvalidRange => errors::INVALID_NONCE_RANGE.to_vec(),
+        NonceError::AlreadyUsed => errors::NONCE_ALREADY_USED.to_vec(),
+    }
+}
+
+/// Menu storage key for a coffee type (`keccak256(coffee_type)`)
+fn menu_key(coffee_type: &str) -> B256 {
+    keccak256(coffee_type.as_bytes())
+}
+
+/// `map_err` adapter for `check_order_total` and `check_quantity`
+fn order_rejected(err: OrderError) -> Vec<u8> {
+    match err {
+        OrderError::NotListed => errors::NOT_ON_MENU.to_vec(),
+        OrderError::PriceMismatch => errors::PRICE_MISMATCH.to_vec(),
+        OrderError::PriceOverflow => errors::PRICE_OVERFLOW.to_vec(),
+        OrderError::QuantityTooLarge => errors::QUANTITY_TOO_LARGE.to_vec(),
+    }
+}
+
+/// `map_err` adapter for `require_pending` and `complete_request`
+fn decryption_rejected(err: DecryptionError) -> Vec<u8> {
+    match err {
+        DecryptionError::UnknownRequest => errors::UNKNOWN_DECRYPTION_REQUEST.to_vec(),
+        DecryptionError::NotReady => errors::DECRYPTION_NOT_READY.to_vec(),
+    }
+}
+
 /// Main storage structure for EVVMCafhe contract
 #[storage]
 #[entrypoint]
 pub struct EVVMCafhe {
+    /// Set by the first `initialize` call; blocks re-initialization
+    initialized: StorageBool,
+
     /// Address of the EVVM Core contract for payment processing
     evvm_core: StorageAddress,
 
     /// Address of the coffee shop owner who can withdraw funds and rewards
     owner_of_shop: StorageAddress,
 
+    /// Proposed new owner, who must call `accept_shop_ownership` to take over
+    pending_owner_of_shop: StorageAddress,
+
     /// Mapping to track used nonces per client address to prevent replay attacks
-    /// client_address => (nonce => is_used)
+    /// client_address => (namespaced_nonce(order_nonce_domain(), nonce) => is_used)
     check_async_nonce: StorageMap<Address, StorageMap<U256, StorageBool>>,
+
+    /// When true, `withdraw_rewards` also requires the caller to be an EVVM staker
+    staker_only_rewards: StorageBool,
+
+    /// Unit price per coffee type, set by the owner
+    /// keccak256(coffee_type) => price (zero means not on the menu)
+    menu: StorageMap<B256, StorageU256>,
+
+    /// Maximum units per order, set by the owner (zero means no limit)
+    max_quantity: StorageU256,
+
+    /// Reentrancy guard, set while a function that calls `EVVMCore.pay` is running
+    /// (only used with the `reentrant` feature)
+    entered: StorageBool,
+
+    /// Pending gateway decryptions requested by the owner
+    /// request_id => ciphertext handle, with a pending flag per request
+    decryption_requests: PendingDecryptions,
+
+    /// FHEVM addresses set by the owner; the gateway calls fall back to the
+    /// compile-time network configuration until `set_fhevm_config` is called
+    fhevm: StoredFHEVMConfig,
 }
 
 /// Public interface for EVVMCafhe contract
@@ -92,11 +229,32 @@ impl EVVMCafhe {
     /// # Parameters
     /// * `evvm_core_address` - Address of the EVVM Core contract
     /// * `owner_of_shop` - Address that will have administrative privileges
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - If `initialize` has already been called
+    /// * `InvalidEvvmCore` - If `evvm_core_address` is zero or has no code
+    ///
+    /// # Security
+    /// Stylus has no constructor, so this is guarded by an `initialized`
+    /// flag instead: only the first call succeeds. Call it right after
+    /// deployment so no one else can initialize first.
+    ///
+    /// EVVMCore must already be deployed: an address with no code (e.g. a
+    /// typo) would make every `pay` revert. A contract whose constructor is
+    /// still running has no code yet either, so deploy EVVMCore in an earlier
+    /// transaction rather than initializing from its constructor.
     pub fn initialize(
         &mut self,
         evvm_core_address: Address,
         owner_of_shop: Address,
     ) -> Result<(), Vec<u8>> {
+        require_not_initialized(self.initialized.get())
+            .map_err(|_| errors::ALREADY_INITIALIZED)?;
+        self.initialized.set(true);
+
+        require_contract(evvm_core_address, evvm_core_address.has_code())
+            .map_err(|_| errors::INVALID_EVVM_CORE)?;
+
         // Set EVVMCore contract address
         self.evvm_core.set(evvm_core_address);
 
@@ -112,7 +270,7 @@ impl EVVMCafhe {
     /// * `client_address` - Address of the customer placing the order
     /// * `coffee_type` - Type/name of coffee being ordered (e.g., "Espresso", "Latte")
     /// * `quantity` - Number of coffee units being ordered
-    /// * `total_price_plaintext` - Total price in plaintext (for signature verification)
+    /// * `total_price_plaintext` - Total price in plaintext; must equal `quantity * menu price`
     /// * `input_encrypted_total_price` - Encrypted total price to be paid in ETH
     /// * `input_price_proof` - Proof for encrypted total price
     /// * `nonce` - Unique number to prevent replay attacks (must not be reused)
@@ -127,9 +285,26 @@ impl EVVMCafhe {
     /// The client must sign: "<evvmID>,orderCoffee,<coffeeType>,<quantity>,<totalPrice>,<nonce>"
     ///
     /// # Errors
+    /// * `QuantityTooLarge` - If `quantity` exceeds the owner-set maximum
+    /// * `NotOnMenu` - If the coffee type has no menu price
+    /// * `PriceMismatch` - If `total_price_plaintext` differs from the menu total
+    /// * `PriceOverflow` - If `quantity * menu price` overflows `U256`
     /// * `InvalidSignature` - If client signature verification fails
     /// * `NonceAlreadyUsed` - If nonce has been previously used
-    /// * `PaymentFailed` - If EVVM payment fails
+    /// * `PaymentFailed` - If EVVM payment fails; known EVVMCore reasons
+    ///   (nonce, signature, token whitelist) are reported as `PaymentFailed: <reason>`
+    /// * `ReentrantCall` - If entered again from within `EVVMCore.pay`
+    ///   (`reentrant` feature only)
+    ///
+    /// # Security
+    /// Checks-effects-interactions: the nonce is marked used before calling
+    /// `pay`, so a reentrant call with the same nonce is rejected. If `pay`
+    /// fails, the whole call reverts and the nonce is unmarked with it.
+    ///
+    /// Stylus SDK 0.6 already reverts reentrant calls unless it is built with
+    /// its `reentrant` feature. With that feature enabled, the `entered` guard
+    /// additionally rejects any nested entry into `order_coffee`,
+    /// `withdraw_rewards` or `withdraw_funds`.
     #[allow(clippy::too_many_arguments)]
     pub fn order_coffee(
         &mut self,
@@ -147,6 +322,14 @@ impl EVVMCafhe {
         nonce_evvm: U256,
         priority_flag_evvm: bool,
     ) -> Result<(), Vec<u8>> {
+        self.enter_non_reentrant()?;
+
+        check_quantity(quantity, self.max_quantity.get()).map_err(order_rejected)?;
+
+        // The price comes from the menu, not from the client
+        let menu_price = self.menu.get(menu_key(&coffee_type));
+        check_order_total(menu_price, quantity, total_price_plaintext).map_err(order_rejected)?;
+
         // Get EVVM Core contract
         let evvm_core_addr = self.evvm_core.get();
         let evvm_core = IEVVMCore::new(evvm_core_addr);
@@ -154,17 +337,16 @@ impl EVVMCafhe {
         // Get EVVM ID for signature verification
         let evvm_id = evvm_core
             .evvm_id(Call::new_in(self))
-            .map_err(|_| errors::PAYMENT_FAILED)?;
+            .map_err(call_failed(errors::PAYMENT_FAILED))?;
 
         // Build the message for signature verification
         // Format: "<evvmID>,orderCoffee,<coffeeType>,<quantity>,<totalPrice>,<nonce>"
-        let inputs = format!(
-            "{},{},{},{}",
-            coffee_type,
-            quantity,
-            total_price_plaintext,
-            nonce
-        );
+        let inputs = SignatureRecover::build_inputs(&[
+            &coffee_type,
+            &quantity.to_string(),
+            &total_price_plaintext.to_string(),
+            &nonce.to_string(),
+        ]);
 
         // Verify client's signature
         let is_valid = SignatureRecover::signature_verification(
@@ -180,16 +362,13 @@ impl EVVMCafhe {
             return Err(errors::INVALID_SIGNATURE.to_vec());
         }
 
-        // Check if nonce has been used before (prevent replay attacks)
-        let nonce_used = self
-            .check_async_nonce
-            .getter(client_address)
-            .getter(nonce)
-            .get();
-
-        if nonce_used {
-            return Err(errors::NONCE_ALREADY_USED.to_vec());
-        }
+        // Mark the nonce used before paying (prevents replay, including from within `pay`)
+        consume_nonce(
+            &mut *self.check_async_nonce.setter(client_address),
+            order_nonce_domain(),
+            nonce,
+        )
+        .map_err(nonce_rejected)?;
 
         // Process the payment through EVVMCore
         evvm_core
@@ -210,13 +389,9 @@ impl EVVMCafhe {
                 Address::ZERO,                                      // executor
                 Vec::new().into(),                                  // signature
             )
-            .map_err(|_| errors::PAYMENT_FAILED)?;
+            .map_err(pay_failed)?;
 
-        // Mark nonce as used
-        self.check_async_nonce
-            .setter(client_address)
-            .setter(nonce)
-            .set(true);
+        self.exit_non_reentrant();
 
         Ok(())
     }
@@ -233,7 +408,8 @@ impl EVVMCafhe {
     /// * `input_fee_proof` - Proof for encrypted priority fee
     ///
     /// # Security
-    /// Only callable by the coffee shop owner
+    /// Only callable by the coffee shop owner. If `staker_only_rewards` is
+    /// enabled, the owner must also be a registered EVVM staker.
     #[allow(clippy::too_many_arguments)]
     pub fn withdraw_rewards(
         &mut self,
@@ -246,9 +422,17 @@ impl EVVMCafhe {
         input_fee_proof: Vec<u8>,
     ) -> Result<(), Vec<u8>> {
         // Check authorization
-        if msg::sender() != self.owner_of_shop.get() {
-            return Err(errors::UNAUTHORIZED.to_vec());
-        }
+        require_owner(msg::sender(), self.owner_of_shop.get())
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        // Optionally restrict reward withdrawals to stakers
+        require_staker(
+            self.staker_only_rewards.get(),
+            || self.is_caller_staker(),
+            errors::NOT_STAKER.to_vec(),
+        )?;
+
+        self.enter_non_reentrant()?;
 
         // Get EVVM Core contract
         let evvm_core_addr = self.evvm_core.get();
@@ -273,7 +457,9 @@ impl EVVMCafhe {
                 Address::ZERO,                                      // executor
                 Vec::new().into(),                                  // signature
             )
-            .map_err(|_| errors::PAYMENT_FAILED)?;
+            .map_err(pay_failed)?;
+
+        self.exit_non_reentrant();
 
         Ok(())
     }
@@ -303,9 +489,10 @@ impl EVVMCafhe {
         input_fee_proof: Vec<u8>,
     ) -> Result<(), Vec<u8>> {
         // Check authorization
-        if msg::sender() != self.owner_of_shop.get() {
-            return Err(errors::UNAUTHORIZED.to_vec());
-        }
+        require_owner(msg::sender(), self.owner_of_shop.get())
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        self.enter_non_reentrant()?;
 
         // Get EVVM Core contract
         let evvm_core_addr = self.evvm_core.get();
@@ -330,21 +517,296 @@ impl EVVMCafhe {
                 Address::ZERO,                                      // executor
                 Vec::new().into(),                                  // signature
             )
-            .map_err(|_| errors::PAYMENT_FAILED)?;
+            .map_err(pay_failed)?;
+
+        self.exit_non_reentrant();
+
+        Ok(())
+    }
+
+    /// Propose a new shop owner (step 1 of 2)
+    ///
+    /// Ownership only moves once `new_owner` calls `accept_shop_ownership`,
+    /// so a typo in the address cannot lock the shop. Proposing
+    /// `Address::ZERO` cancels a pending transfer.
+    ///
+    /// # Security
+    /// Only callable by the coffee shop owner
+    pub fn transfer_shop_ownership(&mut self, new_owner: Address) -> Result<(), Vec<u8>> {
+        let mut transfer = self.shop_ownership();
+        transfer
+            .propose(msg::sender(), new_owner)
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        self.pending_owner_of_shop.set(transfer.pending_owner());
+
+        evm::log(OwnershipTransferStarted {
+            previousOwner: transfer.owner(),
+            newOwner: new_owner,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending ownership transfer (step 2 of 2)
+    ///
+    /// # Security
+    /// Only callable by the pending owner
+    pub fn accept_shop_ownership(&mut self) -> Result<(), Vec<u8>> {
+        let mut transfer = self.shop_ownership();
+        let previous_owner = transfer
+            .accept(msg::sender())
+            .map_err(|_| errors::NOT_PENDING_OWNER)?;
+
+        self.owner_of_shop.set(transfer.owner());
+        self.pending_owner_of_shop.set(transfer.pending_owner());
+
+        evm::log(OwnershipTransferred {
+            previousOwner: previous_owner,
+            newOwner: transfer.owner(),
+        });
+
+        Ok(())
+    }
+
+    /// Set the unit price of a coffee type
+    ///
+    /// A price of zero removes the coffee type from the menu.
+    ///
+    /// # Security
+    /// Only callable by the coffee shop owner
+    pub fn set_price(&mut self, coffee_type: String, price: U256) -> Result<(), Vec<u8>> {
+        require_owner(msg::sender(), self.owner_of_shop.get())
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        self.menu.insert(menu_key(&coffee_type), price);
 
         Ok(())
     }
 
+    /// Set the maximum number of units per order
+    ///
+    /// A value of zero removes the limit.
+    ///
+    /// # Security
+    /// Only callable by the coffee shop owner
+    pub fn set_max_quantity(&mut self, max_quantity: U256) -> Result<(), Vec<u8>> {
+        require_owner(msg::sender(), self.owner_of_shop.get())
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        self.max_quantity.set(max_quantity);
+
+        Ok(())
+    }
+
+    /// Require (or stop requiring) staker status for `withdraw_rewards`
+    ///
+    /// # Security
+    /// Only callable by the coffee shop owner
+    pub fn set_staker_only_rewards(&mut self, enabled: bool) -> Result<(), Vec<u8>> {
+        require_owner(msg::sender(), self.owner_of_shop.get())
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        self.staker_only_rewards.set(enabled);
+
+        Ok(())
+    }
+
+    /// Use FHEVM addresses chosen at deploy time instead of the compile-time network
+    ///
+    /// The decryption functions send their gateway calls to `gateway`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the coffee shop owner
+    /// * `InvalidFhevmConfig` - An address is zero; nothing is stored
+    ///
+    /// # Security
+    /// Only callable by the coffee shop owner
+    pub fn set_fhevm_config(
+        &mut self,
+        fhevm_precompile: Address,
+        input_verifier: Address,
+        acl: Address,
+        gateway: Address,
+        kms_verifier: Address,
+    ) -> Result<(), Vec<u8>> {
+        require_owner(msg::sender(), self.owner_of_shop.get())
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        self.fhevm
+            .store(FHEVMConfig::from_parts(
+                fhevm_precompile,
+                input_verifier,
+                acl,
+                gateway,
+                kms_verifier,
+            ))
+            .map_err(|_| errors::INVALID_FHEVM_CONFIG.to_vec())
+    }
+
+    /// Request decryption of an encrypted shop balance through the Gateway
+    ///
+    /// The shop contract is passed as the requesting user, so it must be
+    /// allowed on `handle` in the ACL; the gateway rejects the request
+    /// otherwise. Read the result later with `finalize_decryption`.
+    ///
+    /// # Returns
+    /// * `U256` - Gateway request ID
+    ///
+    /// # Security
+    /// Only callable by the coffee shop owner
+    pub fn request_balance_decryption(&mut self, handle: Euint64) -> Result<U256, Vec<u8>> {
+        require_owner(msg::sender(), self.owner_of_shop.get())
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        let gateway = Gateway::with_config(self.fhevm.load());
+        let request_id = gateway
+            .request_decryption(handle, contract::address(), Call::new_in(self))
+            .map_err(|_| errors::DECRYPTION_REQUEST_FAILED)?;
+
+        self.decryption_requests.insert(request_id, handle);
+
+        Ok(request_id)
+    }
+
+    /// Read the plaintext of a decryption requested with `request_balance_decryption`
+    ///
+    /// The removed balance getters returned zero when they could not read a
+    /// value, which was indistinguishable from an empty balance. This function
+    /// reverts with `DecryptionNotReady` instead, so a zero result is always a
+    /// real zero.
+    ///
+    /// A request can be finalized once: it is removed from
+    /// `decryption_requests` before the value is read.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the coffee shop owner
+    /// * `UnknownDecryptionRequest` - `request_id` was not issued by this contract,
+    ///   or was already finalized
+    /// * `DecryptionNotReady` - The gateway has not finished decrypting yet
+    /// * `DecryptionRequestFailed` - A gateway call failed
+    ///
+    /// # Security
+    /// Only callable by the coffee shop owner
+    pub fn finalize_decryption(&mut self, request_id: U256) -> Result<U256, Vec<u8>> {
+        require_owner(msg::sender(), self.owner_of_shop.get())
+            .map_err(|_| errors::UNAUTHORIZED)?;
+
+        require_pending(&self.decryption_requests, request_id).map_err(decryption_rejected)?;
+
+        let gateway = Gateway::with_config(self.fhevm.load());
+        let ready = gateway
+            .is_ready(request_id, Call::new_in(self))
+            .map_err(|_| errors::DECRYPTION_REQUEST_FAILED)?;
+        complete_request(&mut self.decryption_requests, request_id, ready)
+            .map_err(decryption_rejected)?;
+
+        gateway
+            .get_value(request_id, Call::new_in(self))
+            .map_err(|_| errors::DECRYPTION_REQUEST_FAILED.to_vec())
+    }
+
+    /// Check whether the caller is registered as a staker in EVVMCore
+    ///
+    /// # Note
+    /// Takes `&mut self` even though it only reads: Stylus needs a mutable
+    /// reference to build the `Call` context for the external view call.
+    pub fn is_caller_staker(&mut self) -> Result<bool, Vec<u8>> {
+        let evvm_core = IEVVMCore::new(self.evvm_core.get());
+
+        evvm_core
+            .is_address_staker(Call::new_in(self), msg::sender())
+            .map_err(call_failed(errors::STAKER_CHECK_FAILED))
+    }
+
+    /// Get the encrypted balance handle of `user` for `token` from EVVMCore
+    ///
+    /// Replaces the removed balance getters, which could not make the call
+    /// and returned a zero handle instead. The handle is returned as-is; the
+    /// caller still needs ACL access to decrypt it.
+    ///
+    /// # Note
+    /// Takes `&mut self` even though it only reads: Stylus needs a mutable
+    /// reference to build the `Call` context for the external view call, so
+    /// this cannot be exposed as a `view` function.
+    ///
+    /// # Errors
+    /// * `BalanceReadFailed` - The `getBalance` call reverted or returned undecodable data
+    pub fn get_encrypted_balance(&mut self, user: Address, token: Address) -> Result<Euint64, Vec<u8>> {
+        let evvm_core = IEVVMCore::new(self.evvm_core.get());
+
+        evvm_core
+            .get_balance(Call::new_in(self), user, token)
+            .map_err(call_failed(errors::BALANCE_READ_FAILED))
+    }
+
+    /// Mark every `orderCoffee` nonce in `[start, start + count)` as used for a client
+    ///
+    /// Lets a client invalidate orders it signed but no longer wants
+    /// submitted. The whole range is checked first: if any nonce is already
+    /// used, nothing is marked.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not `client_address`
+    /// * `InvalidNonceRange` - `count` exceeds `MAX_NONCE_RANGE` (64) or the range overflows
+    /// * `NonceAlreadyUsed` - A nonce in the range is already used
+    ///
+    /// # Security
+    /// Only callable by the client itself, so no one else can cancel its orders
+    pub fn mark_nonce_range(
+        &mut self,
+        client_address: Address,
+        start: U256,
+        count: u64,
+    ) -> Result<(), Vec<u8>> {
+        if msg::sender() != client_address {
+            return Err(errors::UNAUTHORIZED.to_vec());
+        }
+
+        mark_nonce_range(
+            &mut *self.check_async_nonce.setter(client_address),
+            order_nonce_domain(),
+            start,
+            count,
+        )
+        .map_err(nonce_rejected)
+    }
+
     // ============================================================================
     // View Functions
     // ============================================================================
 
     /// Check if a nonce has been used for a specific client
     pub fn is_this_nonce_used(&self, client_address: Address, nonce: U256) -> bool {
-        self.check_async_nonce
-            .getter(client_address)
-            .getter(nonce)
-            .get()
+        is_nonce_used(&*self.check_async_nonce.getter(client_address), order_nonce_domain(), nonce)
+    }
+
+    /// Check that every nonce in `[start, start + count)` is unused for a client
+    ///
+    /// Returns early on the first used nonce. `count` is capped at `MAX_NONCE_RANGE` (64).
+    pub fn are_nonces_unused(
+        &self,
+        client_address: Address,
+        start: U256,
+        count: u64,
+    ) -> Result<bool, Vec<u8>> {
+        are_nonces_unused(
+            &*self.check_async_nonce.getter(client_address),
+            order_nonce_domain(),
+            start,
+            count,
+        )
+        .map_err(nonce_rejected)
+    }
+
+    /// Get the unit price of a coffee type (zero if not on the menu)
+    pub fn get_price(&self, coffee_type: String) -> U256 {
+        self.menu.get(menu_key(&coffee_type))
+    }
+
+    /// Get the maximum number of units per order (zero means no limit)
+    pub fn get_max_quantity(&self) -> U256 {
+        self.max_quantity.get()
     }
 
     /// Get the principal token address
@@ -364,9 +826,8 @@ impl EVVMCafhe {
     // mutable reference to self. These functions created false expectations
     // by appearing to work but always returning FixedBytes::ZERO.
     //
-    // To get encrypted balances, use EVVMCore directly:
-    //   - get_encrypted_eth_balance(shop_address)
-    //   - get_encrypted_token_balance(shop_address, token_id)
+    // To get encrypted balances, use `get_encrypted_balance(user, token)`,
+    // which takes `&mut self` and reverts if the read fails.
     //
     // Removed functions:
     //   - get_amount_of_principal_token_in_shop()
@@ -381,4 +842,57 @@ impl EVVMCafhe {
     pub fn get_owner(&self) -> Address {
         self.owner_of_shop.get()
     }
+
+    /// Get the pending owner address (zero if no transfer is in progress)
+    pub fn get_pending_owner(&self) -> Address {
+        self.pending_owner_of_shop.get()
+    }
+
+    /// Get the ciphertext handle of a pending decryption request
+    ///
+    /// # Errors
+    /// * `UnknownDecryptionRequest` - `request_id` is unknown or already finalized
+    pub fn get_decryption_handle(&self, request_id: U256) -> Result<B256, Vec<u8>> {
+        require_pending(&self.decryption_requests, request_id).map_err(decryption_rejected)
+    }
+
+    /// Get the Gateway address used by the decryption functions
+    pub fn get_gateway_address(&self) -> Address {
+        self.fhevm.load().gateway_address()
+    }
+
+    /// Check whether `withdraw_rewards` requires staker status
+    pub fn is_staker_only_rewards(&self) -> bool {
+        self.staker_only_rewards.get()
+    }
+}
+
+/// Internal helpers (not exposed in the contract ABI)
+impl EVVMCafhe {
+    /// Current owner and pending owner, for `transfer_shop_ownership` and `accept_shop_ownership`
+    fn shop_ownership(&self) -> OwnershipTransfer {
+        OwnershipTransfer::new(self.owner_of_shop.get(), self.pending_owner_of_shop.get())
+    }
+
+    /// Set the reentrancy guard, failing if it is already set
+    ///
+    /// Every error path reverts, which also rolls back the guard, so only
+    /// the success path needs to call `exit_non_reentrant`.
+    ///
+    /// A no-op unless built with the `reentrant` feature: the SDK rejects
+    /// reentrant calls on its own otherwise.
+    fn enter_non_reentrant(&mut self) -> Result<(), Vec<u8>> {
+        #[cfg(feature = "reentrant")]
+        {
+            require_not_entered(self.entered.get()).map_err(|_| errors::REENTRANT_CALL)?;
+            self.entered.set(true);
+        }
+        Ok(())
+    }
+
+    /// Clear the reentrancy guard
+    fn exit_non_reentrant(&mut self) {
+        #[cfg(feature = "reentrant")]
+        self.entered.set(false);
+    }
 }
@@ -9,8 +9,51 @@
 //! sepolia = []
 //! arbitrum-mainnet = []
 //! ```
+//!
+//! Network features are mutually exclusive. Enabling more than one fails the
+//! build, e.g. with both `sepolia` and `arbitrum-mainnet`:
+//!
+//! ```text
+//! error: fhe-stylus: features `sepolia` and `arbitrum-mainnet` are mutually exclusive; enable exactly one network feature
+//! ```
+//!
+//! Contracts deployed against a private FHEVM fork or a redeployed precompile
+//! set can override the compile-time addresses at deploy time by keeping a
+//! [`StoredFHEVMConfig`] in storage, writing it from `initialize`, and running
+//! operations through [`FHE::with_config`](crate::fhe::FHE::with_config).
+
+use alloc::vec::Vec;
+use stylus_sdk::alloy_primitives::{keccak256, Address, B256};
+use stylus_sdk::prelude::*;
+use stylus_sdk::storage::{StorageAddress, StorageBool};
 
-use stylus_sdk::alloy_primitives::Address;
+// Refuse to build with conflicting network features rather than silently
+// picking one in `FHEVMConfig::current()`
+#[cfg(all(feature = "sepolia", feature = "arbitrum-mainnet"))]
+compile_error!("fhe-stylus: features `sepolia` and `arbitrum-mainnet` are mutually exclusive; enable exactly one network feature");
+
+#[cfg(all(feature = "sepolia", feature = "arbitrum-testnet"))]
+compile_error!("fhe-stylus: features `sepolia` and `arbitrum-testnet` are mutually exclusive; enable exactly one network feature");
+
+#[cfg(all(feature = "arbitrum-mainnet", feature = "arbitrum-testnet"))]
+compile_error!("fhe-stylus: features `arbitrum-mainnet` and `arbitrum-testnet` are mutually exclusive; enable exactly one network feature");
+
+/// Errors reported by [`FHEVMConfig::validate`]
+///
+/// Each variant names the address that is still `Address::ZERO`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `fhevm_precompile` is not configured
+    ZeroPrecompile,
+    /// `input_verifier` is not configured
+    ZeroInputVerifier,
+    /// `acl` is not configured
+    ZeroAcl,
+    /// `gateway` is not configured
+    ZeroGateway,
+    /// `kms_verifier` is not configured
+    ZeroKmsVerifier,
+}
 
 /// Configuration for FHEVM precompile addresses on a specific network
 #[derive(Debug, Clone, Copy)]
@@ -32,6 +75,26 @@ pub struct FHEVMConfig {
 }
 
 impl FHEVMConfig {
+    /// Build a configuration from explicit precompile addresses
+    ///
+    /// Use this when the addresses are only known at deploy time, e.g. for a
+    /// private FHEVM fork. The result can be persisted with [`StoredFHEVMConfig`].
+    pub const fn from_parts(
+        fhevm_precompile: Address,
+        input_verifier: Address,
+        acl: Address,
+        gateway: Address,
+        kms_verifier: Address,
+    ) -> Self {
+        Self {
+            fhevm_precompile,
+            input_verifier,
+            acl,
+            gateway,
+            kms_verifier,
+        }
+    }
+
     /// Get the configuration for the current network based on cargo features
     pub const fn current() -> Self {
         #[cfg(feature = "sepolia")]
@@ -150,6 +213,16 @@ pub const fn get_config() -> FHEVMConfig {
     FHEVMConfig::current()
 }
 
+/// Fingerprint of the active network configuration
+///
+/// Record this at deployment (e.g. from an off-chain call or an event) and
+/// compare it later to detect bytecode built against a different network
+/// feature, a different crate version, or changed precompile addresses.
+/// See [`FHEVMConfig::fingerprint`].
+pub fn config_fingerprint() -> B256 {
+    get_config().fingerprint()
+}
+
 /// Precompile address getters for convenience
 impl FHEVMConfig {
     /// Get the FHEVM operations precompile address
@@ -176,12 +249,189 @@ impl FHEVMConfig {
     pub const fn kms_verifier_address(&self) -> Address {
         self.kms_verifier
     }
+
+    /// Check that every address in this configuration is set
+    ///
+    /// Networks without an FHEVM deployment use placeholder zero addresses,
+    /// and a call to `Address::ZERO` only fails later with an opaque revert.
+    /// Call this from `initialize` to fail fast instead.
+    ///
+    /// # Errors
+    /// The first zero address, in field order.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.fhevm_precompile == Address::ZERO {
+            return Err(ConfigError::ZeroPrecompile);
+        }
+        if self.input_verifier == Address::ZERO {
+            return Err(ConfigError::ZeroInputVerifier);
+        }
+        if self.acl == Address::ZERO {
+            return Err(ConfigError::ZeroAcl);
+        }
+        if self.gateway == Address::ZERO {
+            return Err(ConfigError::ZeroGateway);
+        }
+        if self.kms_verifier == Address::ZERO {
+            return Err(ConfigError::ZeroKmsVerifier);
+        }
+        Ok(())
+    }
+
+    /// Hash of all addresses in this configuration plus the crate [`VERSION`](crate::VERSION)
+    ///
+    /// ```text
+    /// keccak256(fhevm_precompile || input_verifier || acl || gateway || kms_verifier || VERSION)
+    /// ```
+    ///
+    /// Addresses are packed as 20 bytes each, followed by the UTF-8 version string.
+    pub fn fingerprint(&self) -> B256 {
+        let mut packed = Vec::with_capacity(5 * 20 + crate::VERSION.len());
+        packed.extend_from_slice(self.fhevm_precompile.as_slice());
+        packed.extend_from_slice(self.input_verifier.as_slice());
+        packed.extend_from_slice(self.acl.as_slice());
+        packed.extend_from_slice(self.gateway.as_slice());
+        packed.extend_from_slice(self.kms_verifier.as_slice());
+        packed.extend_from_slice(crate::VERSION.as_bytes());
+
+        keccak256(&packed)
+    }
+}
+
+/// Storage-backed FHEVM configuration
+///
+/// Embed this in a contract's storage to choose precompile addresses at deploy
+/// time instead of compile time. Until [`StoredFHEVMConfig::store`] succeeds,
+/// [`StoredFHEVMConfig::load`] falls back to the cargo-feature configuration.
+/// The `FHE` associated functions always use the compile-time configuration;
+/// pass the loaded one to [`FHE::with_config`](crate::fhe::FHE::with_config).
+///
+/// # Example
+/// ```ignore
+/// #[storage]
+/// #[entrypoint]
+/// pub struct MyContract {
+///     fhevm: StoredFHEVMConfig,
+/// }
+///
+/// #[public]
+/// impl MyContract {
+///     pub fn initialize(&mut self, precompile: Address, verifier: Address, acl: Address,
+///                       gateway: Address, kms: Address) -> Result<(), Vec<u8>> {
+///         self.fhevm
+///             .store(FHEVMConfig::from_parts(precompile, verifier, acl, gateway, kms))
+///             .map_err(|_| b"Invalid FHEVM config".to_vec())
+///     }
+///
+///     pub fn add_fee(&mut self, balance: Euint64) -> Result<Euint64, Vec<u8>> {
+///         FHE::with_config(self.fhevm.load())
+///             .add(balance.into(), U256::from(10).into(), Call::new_in(self))
+///             .map_err(|_| b"FHE add failed".to_vec())
+///     }
+/// }
+/// ```
+#[storage]
+pub struct StoredFHEVMConfig {
+    fhevm_precompile: StorageAddress,
+    input_verifier: StorageAddress,
+    acl: StorageAddress,
+    gateway: StorageAddress,
+    kms_verifier: StorageAddress,
+    /// Set once a validated configuration has been stored
+    configured: StorageBool,
+}
+
+impl StoredFHEVMConfig {
+    /// Persist a configuration, overriding the compile-time default
+    ///
+    /// # Errors
+    /// * `ConfigError` - An address in `config` is zero; nothing is stored
+    pub fn store(&mut self, config: FHEVMConfig) -> Result<(), ConfigError> {
+        config.validate()?;
+
+        self.fhevm_precompile.set(config.fhevm_precompile);
+        self.input_verifier.set(config.input_verifier);
+        self.acl.set(config.acl);
+        self.gateway.set(config.gateway);
+        self.kms_verifier.set(config.kms_verifier);
+        self.configured.set(true);
+        Ok(())
+    }
+
+    /// Whether a custom configuration has been stored
+    pub fn is_set(&self) -> bool {
+        self.configured.get()
+    }
+
+    /// Load the stored configuration, or [`FHEVMConfig::current`] if none was stored
+    pub fn load(&self) -> FHEVMConfig {
+        if !self.is_set() {
+            return FHEVMConfig::current();
+        }
+
+        FHEVMConfig::from_parts(
+            self.fhevm_precompile.get(),
+            self.input_verifier.get(),
+            self.acl.get(),
+            self.gateway.get(),
+            self.kms_verifier.get(),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_parts() {
+        let config = FHEVMConfig::from_parts(
+            Address::repeat_byte(0x01),
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x04),
+            Address::repeat_byte(0x05),
+        );
+        assert_eq!(config.precompile_address(), Address::repeat_byte(0x01));
+        assert_eq!(config.input_verifier_address(), Address::repeat_byte(0x02));
+        assert_eq!(config.acl_address(), Address::repeat_byte(0x03));
+        assert_eq!(config.gateway_address(), Address::repeat_byte(0x04));
+        assert_eq!(config.kms_verifier_address(), Address::repeat_byte(0x05));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(FHEVMConfig::sepolia().validate(), Ok(()));
+        assert_eq!(
+            FHEVMConfig::arbitrum_mainnet().validate(),
+            Err(ConfigError::ZeroPrecompile)
+        );
+
+        // Reports the specific zero field
+        let mut config = FHEVMConfig::sepolia();
+        config.acl = Address::ZERO;
+        assert_eq!(config.validate(), Err(ConfigError::ZeroAcl));
+        config.acl = Address::repeat_byte(0x03);
+        config.kms_verifier = Address::ZERO;
+        assert_eq!(config.validate(), Err(ConfigError::ZeroKmsVerifier));
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let config = FHEVMConfig::sepolia();
+        assert_eq!(config.fingerprint(), FHEVMConfig::sepolia().fingerprint());
+
+        // Any changed address changes the fingerprint
+        let mut other = config;
+        other.gateway = Address::repeat_byte(0x04);
+        assert_ne!(config.fingerprint(), other.fingerprint());
+
+        assert_ne!(
+            FHEVMConfig::sepolia().fingerprint(),
+            FHEVMConfig::arbitrum_mainnet().fingerprint()
+        );
+        assert_eq!(config_fingerprint(), get_config().fingerprint());
+    }
+
     #[test]
     fn test_sepolia_config() {
         let config = FHEVMConfig::sepolia();
@@ -0,0 +1,42 @@
+//! Big-Endian `FixedBytes<32>` / `U256` Conversions
+//!
+//! Matches Solidity's `bytes32(uint256(x))` and `uint256(bytes32(b))`: the
+//! most significant byte of the integer is byte 0 of the `bytes32`. Use these
+//! instead of ad-hoc `to_be_bytes` calls so every call site agrees.
+
+use stylus_sdk::alloy_primitives::{FixedBytes, U256};
+
+/// `bytes32(uint256(value))`
+pub fn u256_to_b256(value: U256) -> FixedBytes<32> {
+    FixedBytes(value.to_be_bytes::<32>())
+}
+
+/// `uint256(bytes32(value))`
+pub fn b256_to_u256(value: FixedBytes<32>) -> U256 {
+    U256::from_be_bytes(value.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(u256_to_b256(U256::ZERO), FixedBytes::ZERO);
+        assert_eq!(b256_to_u256(FixedBytes::ZERO), U256::ZERO);
+    }
+
+    #[test]
+    fn test_one_is_big_endian() {
+        let one = u256_to_b256(U256::from(1));
+        assert_eq!(one[31], 1);
+        assert!(one[..31].iter().all(|b| *b == 0));
+        assert_eq!(b256_to_u256(one), U256::from(1));
+    }
+
+    #[test]
+    fn test_max() {
+        assert_eq!(u256_to_b256(U256::MAX), FixedBytes::repeat_byte(0xff));
+        assert_eq!(b256_to_u256(FixedBytes::repeat_byte(0xff)), U256::MAX);
+    }
+}
@@ -46,12 +46,19 @@
 //! }
 //! ```
 
+use stylus_sdk::alloy_primitives::{Address, FixedBytes, U256};
+use stylus_sdk::call::{NonPayableCallContext, StaticCallContext};
+
+use crate::config::{get_config, FHEVMConfig};
+use crate::convert::u256_to_b256;
+use crate::interfaces::{IFHEVMPrecompile, IInputVerifier, IACL, SCALAR_ENCRYPTED, SCALAR_PLAIN};
 use crate::types::*;
 
 /// Main FHE operations struct
 ///
-/// This provides documentation for FHE operations. For actual implementation,
-/// use the precompile interfaces directly (see module documentation).
+/// Wraps input verification, the precompile operations and ACL grants against
+/// the compile-time configuration. For anything not covered here, use the
+/// precompile interfaces directly (see module documentation).
 pub struct FHE;
 
 /// Errors that can occur during FHE operations
@@ -69,42 +76,1156 @@ pub enum FHEError {
     OperationFailed,
 }
 
+/// Operand of a binary FHE operation
+///
+/// ```ignore
+/// // balance + 10
+/// let total = FHE::add(balance.into(), U256::from(10).into(), Call::new_in(self))?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    /// Handle to an encrypted value
+    Encrypted(Euint64),
+    /// Plaintext value, sent to the precompile with `SCALAR_PLAIN`
+    Plain(U256),
+}
+
+impl From<Euint64> for Operand {
+    fn from(handle: Euint64) -> Self {
+        Operand::Encrypted(handle)
+    }
+}
+
+impl From<U256> for Operand {
+    fn from(value: U256) -> Self {
+        Operand::Plain(value)
+    }
+}
+
+/// Minimum accepted input proof length in bytes
+pub const MIN_PROOF_LEN: usize = 1;
+
+/// Maximum accepted input proof length in bytes
+///
+/// Generous upper bound; real input proofs are far smaller. Anything above
+/// this is rejected before spending gas on the verifier call.
+pub const MAX_PROOF_LEN: usize = 16 * 1024;
+
 impl FHE {
-    /// Verify and convert an external encrypted value (stub)
+    /// Operations against an explicit configuration instead of [`get_config`]
+    ///
+    /// See [`ConfiguredFHE`].
+    pub fn with_config(config: FHEVMConfig) -> ConfiguredFHE {
+        ConfiguredFHE { config }
+    }
+
+    /// Operations against the compile-time configuration
+    fn configured() -> ConfiguredFHE {
+        Self::with_config(get_config())
+    }
+
+    /// Cheap pre-check on an input proof before calling the verifier
+    ///
+    /// # Errors
+    /// * `FHEError::InvalidProof` - Proof is shorter than `MIN_PROOF_LEN`
+    ///   or longer than `MAX_PROOF_LEN`
+    pub fn validate_proof(proof: &[u8]) -> Result<(), FHEError> {
+        if proof.len() < MIN_PROOF_LEN || proof.len() > MAX_PROOF_LEN {
+            return Err(FHEError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Verify an external encrypted value of any supported width
+    ///
+    /// Calls `IInputVerifier::verifyInput` with the given type constant
+    /// (`EUINT8_TYPE` ... `EUINT256_TYPE`, `EBOOL_TYPE`, `EADDRESS_TYPE`).
+    /// Prefer the width-specific helpers such as [`FHE::from_external_u8`].
+    ///
+    /// # Errors
+    /// * `FHEError::InvalidInput` - Unknown type constant, or verification failed
+    /// * `FHEError::InvalidProof` - Proof is empty or oversized
+    pub fn from_external_typed(
+        input: FixedBytes<32>,
+        proof: &[u8],
+        input_type: u8,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<FixedBytes<32>, FHEError> {
+        Self::configured().from_external_typed(input, proof, input_type, call_ctx)
+    }
+
+    /// Verify an external encrypted 64-bit value against an explicit verifier
+    ///
+    /// Same as [`FHE::from_external_u64`], but calls `verifier` instead of the
+    /// compile-time `get_config().input_verifier_address()`. Use this for
+    /// forked networks or a verifier address loaded from
+    /// [`StoredFHEVMConfig`](crate::config::StoredFHEVMConfig).
+    ///
+    /// # Errors
+    /// * `FHEError::InvalidInput` - Verification failed
+    /// * `FHEError::InvalidProof` - Proof is empty or oversized
+    pub fn from_external_with_verifier(
+        input: ExternalEuint64,
+        proof: &[u8],
+        verifier: Address,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::validate_proof(proof)?;
+
+        IInputVerifier::new(verifier)
+            .verify_input(
+                call_ctx,
+                input,
+                proof.to_vec().into(),
+                EncryptedType::Uint64.as_zama_u8(),
+            )
+            .map_err(|_| FHEError::InvalidInput)
+    }
+
+    /// Verify an external encrypted 8-bit value
+    pub fn from_external_u8(
+        input: ExternalEuint8,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint8, FHEError> {
+        Self::configured().from_external_u8(input, proof, call_ctx)
+    }
+
+    /// Verify an external encrypted 16-bit value
+    pub fn from_external_u16(
+        input: ExternalEuint16,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint16, FHEError> {
+        Self::configured().from_external_u16(input, proof, call_ctx)
+    }
+
+    /// Verify an external encrypted 32-bit value
+    pub fn from_external_u32(
+        input: ExternalEuint32,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint32, FHEError> {
+        Self::configured().from_external_u32(input, proof, call_ctx)
+    }
+
+    /// Verify an external encrypted 64-bit value
+    pub fn from_external_u64(
+        input: ExternalEuint64,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().from_external_u64(input, proof, call_ctx)
+    }
+
+    /// Verify an external encrypted 128-bit value
+    pub fn from_external_u128(
+        input: ExternalEuint128,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint128, FHEError> {
+        Self::configured().from_external_u128(input, proof, call_ctx)
+    }
+
+    /// Verify an external encrypted 256-bit value
+    pub fn from_external_u256(
+        input: ExternalEuint256,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint256, FHEError> {
+        Self::configured().from_external_u256(input, proof, call_ctx)
+    }
+
+    /// Verify an external encrypted value bound to a specific user
+    ///
+    /// Calls `IInputVerifier::verifyInputWithSignature` with `EUINT64_TYPE`.
+    ///
+    /// Binding the input to `user` matters for confidential transfers: a
+    /// ciphertext and proof seen in the mempool or in another transaction
+    /// cannot be resubmitted by a different account as its own amount, since
+    /// verification fails for any address other than the one it was bound to.
+    ///
+    /// # Parameters
+    /// * `input` - The encrypted input handle from the user
+    /// * `proof` - Zero-knowledge proof of correct encryption
+    /// * `user` - Address the input was encrypted for (usually `msg::sender()`)
+    /// * `call_ctx` - Call context, e.g. `Call::new_in(self)`
+    pub fn from_external_with_signature(
+        input: ExternalEuint64,
+        proof: &[u8],
+        user: Address,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().from_external_with_signature(input, proof, user, call_ctx)
+    }
+
+    /// Generate an encrypted random value
     ///
-    /// **Use `IInputVerifier::verify_input()` directly in your contract instead.**
-    pub fn from_external(_input: ExternalEuint64, _proof: &[u8]) -> Result<Euint64, FHEError> {
-        Err(FHEError::OperationFailed)
+    /// # Parameters
+    /// * `rand_type` - Type constant of the result (`EUINT8_TYPE` ... `EUINT256_TYPE`, `EBOOL_TYPE`)
+    /// * `call_ctx` - Call context, e.g. `Call::new_in(self)`
+    ///
+    /// # Errors
+    /// * `FHEError::InvalidInput` - `EADDRESS_TYPE` or unknown type constant
+    /// * `FHEError::PrecompileCallFailed` - Precompile call failed
+    pub fn rand(rand_type: u8, call_ctx: impl NonPayableCallContext) -> Result<Euint64, FHEError> {
+        Self::configured().rand(rand_type, call_ctx)
+    }
+
+    /// Generate an encrypted random value in `[0, upper_bound)`
+    ///
+    /// # Parameters
+    /// * `upper_bound` - Exclusive upper bound; a power of two no larger than
+    ///   `2^bits` of `rand_type`, as `fheRandBounded` requires
+    /// * `rand_type` - Type constant of the result (`EUINT8_TYPE` ... `EUINT256_TYPE`)
+    /// * `call_ctx` - Call context, e.g. `Call::new_in(self)`
+    ///
+    /// # Errors
+    /// * `FHEError::InvalidInput` - `upper_bound` is zero, not a power of two or
+    ///   wider than the type, or `rand_type` is not an unsigned integer type
+    /// * `FHEError::PrecompileCallFailed` - Precompile call failed
+    pub fn rand_bounded(
+        upper_bound: U256,
+        rand_type: u8,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().rand_bounded(upper_bound, rand_type, call_ctx)
+    }
+
+    /// Select between two encrypted values based on an encrypted condition
+    ///
+    /// Returns `if_true` where `cond` decrypts to true, `if_false` otherwise,
+    /// without revealing which branch was taken. All three operands must be
+    /// encrypted handles; there is no scalar form of `fheIfThenElse`.
+    ///
+    /// # Errors
+    /// * `FHEError::PrecompileCallFailed` - Precompile call failed
+    pub fn if_then_else(
+        cond: Ebool,
+        if_true: Euint64,
+        if_false: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().if_then_else(cond, if_true, if_false, call_ctx)
+    }
+
+    // ============ Comparison Operations ============
+    //
+    // Each comparison returns an encrypted boolean. The `_scalar` variants
+    // compare against a plaintext right-hand side (`SCALAR_PLAIN`), which is
+    // cheaper than encrypting a constant first.
+
+    /// Encrypted `lhs == rhs`
+    pub fn eq(
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().eq(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs == rhs` against a plaintext `rhs`
+    pub fn eq_scalar(
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().eq_scalar(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs != rhs`
+    pub fn ne(
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().ne(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs != rhs` against a plaintext `rhs`
+    pub fn ne_scalar(
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().ne_scalar(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs < rhs`
+    pub fn lt(
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().lt(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs < rhs` against a plaintext `rhs`
+    pub fn lt_scalar(
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().lt_scalar(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs > rhs`
+    pub fn gt(
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().gt(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs > rhs` against a plaintext `rhs`
+    pub fn gt_scalar(
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().gt_scalar(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs <= rhs`
+    pub fn le(
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().le(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs <= rhs` against a plaintext `rhs`
+    pub fn le_scalar(
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().le_scalar(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs >= rhs`
+    pub fn ge(
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().ge(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted `lhs >= rhs` against a plaintext `rhs`
+    pub fn ge_scalar(
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        Self::configured().ge_scalar(lhs, rhs, call_ctx)
+    }
+
+    // ============ Min / Max / Unary Operations ============
+
+    /// Encrypted minimum of `lhs` and `rhs`
+    pub fn min(
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().min(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted minimum of `lhs` and a plaintext `rhs`
+    pub fn min_scalar(
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().min_scalar(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted maximum of `lhs` and `rhs`
+    pub fn max(
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().max(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted maximum of `lhs` and a plaintext `rhs`
+    pub fn max_scalar(
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().max_scalar(lhs, rhs, call_ctx)
+    }
+
+    /// Encrypted two's-complement negation (wraps modulo 2^64)
+    pub fn neg(ct: Euint64, call_ctx: impl StaticCallContext) -> Result<Euint64, FHEError> {
+        Self::configured().neg(ct, call_ctx)
+    }
+
+    /// Encrypted bitwise NOT
+    pub fn not(ct: Euint64, call_ctx: impl StaticCallContext) -> Result<Euint64, FHEError> {
+        Self::configured().not(ct, call_ctx)
     }
 
-    /// Add two encrypted integers (stub)
+    // ============ Arithmetic Operations ============
+    //
+    // At most one operand may be `Operand::Plain`; the precompile then runs in
+    // `SCALAR_PLAIN` mode with the plaintext in the right-hand slot.
+
+    /// Encrypted `lhs + rhs`
     ///
-    /// **Use `IFHEVMPrecompile::fhe_add()` directly in your contract instead.**
-    pub fn add(_lhs: Euint64, _rhs: Euint64) -> Result<Euint64, FHEError> {
-        Err(FHEError::OperationFailed)
+    /// # Errors
+    /// * `FHEError::InvalidInput` - Both operands are plaintext
+    /// * `FHEError::PrecompileCallFailed` - Precompile call failed
+    pub fn add(
+        lhs: Operand,
+        rhs: Operand,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().add(lhs, rhs, call_ctx)
     }
 
-    /// Subtract two encrypted integers (stub)
+    /// Encrypted `lhs - rhs`
     ///
-    /// **Use `IFHEVMPrecompile::fhe_sub()` directly in your contract instead.**
-    pub fn sub(_lhs: Euint64, _rhs: Euint64) -> Result<Euint64, FHEError> {
-        Err(FHEError::OperationFailed)
+    /// Subtraction is not commutative, so a plaintext operand must be `rhs`.
+    ///
+    /// # Errors
+    /// * `FHEError::InvalidInput` - Both operands are plaintext, or `lhs` is plaintext
+    /// * `FHEError::PrecompileCallFailed` - Precompile call failed
+    pub fn sub(
+        lhs: Operand,
+        rhs: Operand,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().sub(lhs, rhs, call_ctx)
     }
 
-    /// Multiply two encrypted integers (stub)
+    /// Encrypted `lhs * rhs`
     ///
-    /// **Use `IFHEVMPrecompile::fhe_mul()` directly in your contract instead.**
-    pub fn mul(_lhs: Euint64, _rhs: Euint64) -> Result<Euint64, FHEError> {
-        Err(FHEError::OperationFailed)
+    /// # Errors
+    /// * `FHEError::InvalidInput` - Both operands are plaintext
+    /// * `FHEError::PrecompileCallFailed` - Precompile call failed
+    pub fn mul(
+        lhs: Operand,
+        rhs: Operand,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        Self::configured().mul(lhs, rhs, call_ctx)
     }
 
-    /// Grant access to an encrypted value (stub)
+    /// Grant `account` access to an encrypted value through the ACL
+    ///
+    /// Works for a handle of any width. The calling contract must itself be
+    /// allowed on `handle`, which holds for handles it produced.
     ///
-    /// **Use `IACL::allow()` directly in your contract instead.**
-    pub fn allow(_handle: Euint64, _account: stylus_sdk::alloy_primitives::Address) -> Result<(), FHEError> {
-        Err(FHEError::OperationFailed)
+    /// # Errors
+    /// * `FHEError::AccessDenied` - ACL call failed, e.g. the caller is not allowed on `handle`
+    pub fn allow(
+        handle: FixedBytes<32>,
+        account: Address,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<(), FHEError> {
+        Self::configured().allow(handle, account, call_ctx)
+    }
+}
+
+/// FHE operations against an explicit [`FHEVMConfig`]
+///
+/// The associated functions on [`FHE`] use the compile-time [`get_config`].
+/// Build one of these with [`FHE::with_config`] to use addresses chosen at
+/// deploy time instead, e.g. from a [`StoredFHEVMConfig`](crate::config::StoredFHEVMConfig):
+///
+/// ```ignore
+/// let fhe = FHE::with_config(self.fhevm.load());
+/// let total = fhe.add(balance.into(), amount.into(), Call::new_in(self))?;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConfiguredFHE {
+    config: FHEVMConfig,
+}
+
+impl ConfiguredFHE {
+    /// The configuration these operations use
+    pub fn config(&self) -> &FHEVMConfig {
+        &self.config
+    }
+
+    /// See [`FHE::from_external_typed`]
+    pub fn from_external_typed(
+        &self,
+        input: FixedBytes<32>,
+        proof: &[u8],
+        input_type: u8,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<FixedBytes<32>, FHEError> {
+        if EncryptedType::from_zama_u8(input_type).is_none() {
+            return Err(FHEError::InvalidInput);
+        }
+        FHE::validate_proof(proof)?;
+
+        IInputVerifier::new(self.config.input_verifier_address())
+            .verify_input(call_ctx, input, proof.to_vec().into(), input_type)
+            .map_err(|_| FHEError::InvalidInput)
+    }
+
+    /// See [`FHE::from_external_u8`]
+    pub fn from_external_u8(
+        &self,
+        input: ExternalEuint8,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint8, FHEError> {
+        self.from_external_typed(input, proof, EncryptedType::Uint8.as_zama_u8(), call_ctx)
+    }
+
+    /// See [`FHE::from_external_u16`]
+    pub fn from_external_u16(
+        &self,
+        input: ExternalEuint16,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint16, FHEError> {
+        self.from_external_typed(input, proof, EncryptedType::Uint16.as_zama_u8(), call_ctx)
+    }
+
+    /// See [`FHE::from_external_u32`]
+    pub fn from_external_u32(
+        &self,
+        input: ExternalEuint32,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint32, FHEError> {
+        self.from_external_typed(input, proof, EncryptedType::Uint32.as_zama_u8(), call_ctx)
+    }
+
+    /// See [`FHE::from_external_u64`]
+    pub fn from_external_u64(
+        &self,
+        input: ExternalEuint64,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint64, FHEError> {
+        self.from_external_typed(input, proof, EncryptedType::Uint64.as_zama_u8(), call_ctx)
+    }
+
+    /// See [`FHE::from_external_u128`]
+    pub fn from_external_u128(
+        &self,
+        input: ExternalEuint128,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint128, FHEError> {
+        self.from_external_typed(input, proof, EncryptedType::Uint128.as_zama_u8(), call_ctx)
+    }
+
+    /// See [`FHE::from_external_u256`]
+    pub fn from_external_u256(
+        &self,
+        input: ExternalEuint256,
+        proof: &[u8],
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint256, FHEError> {
+        self.from_external_typed(input, proof, EncryptedType::Uint256.as_zama_u8(), call_ctx)
+    }
+
+    /// See [`FHE::from_external_with_signature`]
+    pub fn from_external_with_signature(
+        &self,
+        input: ExternalEuint64,
+        proof: &[u8],
+        user: Address,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint64, FHEError> {
+        FHE::validate_proof(proof)?;
+
+        IInputVerifier::new(self.config.input_verifier_address())
+            .verify_input_with_signature(
+                call_ctx,
+                input,
+                proof.to_vec().into(),
+                EncryptedType::Uint64.as_zama_u8(),
+                user,
+            )
+            .map_err(|_| FHEError::InvalidInput)
+    }
+
+    /// See [`FHE::rand`]
+    pub fn rand(
+        &self,
+        rand_type: u8,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint64, FHEError> {
+        let rand_type = rand_args(rand_type)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_rand(call_ctx, rand_type)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::rand_bounded`]
+    pub fn rand_bounded(
+        &self,
+        upper_bound: U256,
+        rand_type: u8,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<Euint64, FHEError> {
+        let (upper_bound, rand_type) = rand_bounded_args(upper_bound, rand_type)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_rand_bounded(call_ctx, upper_bound, rand_type)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::if_then_else`]
+    pub fn if_then_else(
+        &self,
+        cond: Ebool,
+        if_true: Euint64,
+        if_false: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_if_then_else(call_ctx, cond, if_true, if_false)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::eq`]
+    pub fn eq(
+        &self,
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_eq(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::eq_scalar`]
+    pub fn eq_scalar(
+        &self,
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_eq(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::ne`]
+    pub fn ne(
+        &self,
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_ne(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::ne_scalar`]
+    pub fn ne_scalar(
+        &self,
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_ne(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::lt`]
+    pub fn lt(
+        &self,
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_lt(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::lt_scalar`]
+    pub fn lt_scalar(
+        &self,
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_lt(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::gt`]
+    pub fn gt(
+        &self,
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_gt(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::gt_scalar`]
+    pub fn gt_scalar(
+        &self,
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_gt(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::le`]
+    pub fn le(
+        &self,
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_le(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::le_scalar`]
+    pub fn le_scalar(
+        &self,
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_le(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::ge`]
+    pub fn ge(
+        &self,
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_ge(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::ge_scalar`]
+    pub fn ge_scalar(
+        &self,
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Ebool, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs.into(), rhs.into(), false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_ge(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::min`]
+    pub fn min(
+        &self,
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_min(call_ctx, lhs, rhs, scalar_byte(SCALAR_ENCRYPTED))
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::min_scalar`]
+    pub fn min_scalar(
+        &self,
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_min(call_ctx, lhs, plain_operand(rhs), scalar_byte(SCALAR_PLAIN))
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::max`]
+    pub fn max(
+        &self,
+        lhs: Euint64,
+        rhs: Euint64,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_max(call_ctx, lhs, rhs, scalar_byte(SCALAR_ENCRYPTED))
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::max_scalar`]
+    pub fn max_scalar(
+        &self,
+        lhs: Euint64,
+        rhs: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_max(call_ctx, lhs, plain_operand(rhs), scalar_byte(SCALAR_PLAIN))
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::neg`]
+    pub fn neg(&self, ct: Euint64, call_ctx: impl StaticCallContext) -> Result<Euint64, FHEError> {
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_neg(call_ctx, ct)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::not`]
+    pub fn not(&self, ct: Euint64, call_ctx: impl StaticCallContext) -> Result<Euint64, FHEError> {
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_not(call_ctx, ct)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::add`]
+    pub fn add(
+        &self,
+        lhs: Operand,
+        rhs: Operand,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs, rhs, true)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_add(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::sub`]
+    pub fn sub(
+        &self,
+        lhs: Operand,
+        rhs: Operand,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs, rhs, false)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_sub(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::mul`]
+    pub fn mul(
+        &self,
+        lhs: Operand,
+        rhs: Operand,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<Euint64, FHEError> {
+        let (lhs, rhs, scalar) = binary_operands(lhs, rhs, true)?;
+
+        IFHEVMPrecompile::new(self.config.precompile_address())
+            .fhe_mul(call_ctx, lhs, rhs, scalar)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`FHE::allow`]
+    pub fn allow(
+        &self,
+        handle: FixedBytes<32>,
+        account: Address,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<(), FHEError> {
+        IACL::new(self.config.acl_address())
+            .allow(call_ctx, handle, account)
+            .map_err(|_| FHEError::AccessDenied)
+    }
+}
+
+/// Precompile `scalarByte` for an operand mode (`SCALAR_ENCRYPTED` or `SCALAR_PLAIN`)
+fn scalar_byte(mode: u8) -> FixedBytes<1> {
+    FixedBytes([mode])
+}
+
+/// Encode a plaintext right-hand operand in the `bytes32` slot (big-endian)
+fn plain_operand(value: U256) -> FixedBytes<32> {
+    u256_to_b256(value)
+}
+
+/// Lower two operands to the precompile's `(lhs, rhs, scalarByte)` arguments
+///
+/// The precompile only accepts a plaintext on the right. For commutative
+/// operations a plaintext `lhs` is swapped to the right; otherwise it is rejected.
+fn binary_operands(
+    lhs: Operand,
+    rhs: Operand,
+    commutative: bool,
+) -> Result<(FixedBytes<32>, FixedBytes<32>, FixedBytes<1>), FHEError> {
+    match (lhs, rhs) {
+        (Operand::Encrypted(lhs), Operand::Encrypted(rhs)) => {
+            Ok((lhs, rhs, scalar_byte(SCALAR_ENCRYPTED)))
+        }
+        (Operand::Encrypted(lhs), Operand::Plain(rhs)) => {
+            Ok((lhs, plain_operand(rhs), scalar_byte(SCALAR_PLAIN)))
+        }
+        (Operand::Plain(lhs), Operand::Encrypted(rhs)) if commutative => {
+            Ok((rhs, plain_operand(lhs), scalar_byte(SCALAR_PLAIN)))
+        }
+        _ => Err(FHEError::InvalidInput),
+    }
+}
+
+/// Validate the type argument to `fheRand`: integers and bool, never address
+///
+/// Returns the `randType` argument to pass to the precompile.
+fn rand_args(rand_type: u8) -> Result<u8, FHEError> {
+    match EncryptedType::from_zama_u8(rand_type) {
+        Some(EncryptedType::Address) | None => Err(FHEError::InvalidInput),
+        Some(_) => Ok(rand_type),
     }
 }
 
+/// Validate arguments to `fheRandBounded` before calling the precompile
+///
+/// Only unsigned integer types take a bound, and the bound must be a power of
+/// two that fits the type (`2^bits` itself means the full range). Returns the
+/// `(upperBound, randType)` arguments to pass to the precompile.
+fn rand_bounded_args(upper_bound: U256, rand_type: u8) -> Result<(U256, u8), FHEError> {
+    let ty = EncryptedType::from_zama_u8(rand_type)
+        .filter(|ty| ty.is_integer())
+        .ok_or(FHEError::InvalidInput)?;
+
+    if !upper_bound.is_power_of_two() || upper_bound.bit_len() - 1 > ty.bit_width() as usize {
+        return Err(FHEError::InvalidInput);
+    }
+    Ok((upper_bound, rand_type))
+}
+
 // Re-export for convenience
 pub use FHEError as Error;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_validate_proof_empty() {
+        assert!(matches!(
+            FHE::validate_proof(&[]),
+            Err(FHEError::InvalidProof)
+        ));
+    }
+
+    #[test]
+    fn test_validate_proof_oversized() {
+        let proof = vec![0u8; MAX_PROOF_LEN + 1];
+        assert!(matches!(
+            FHE::validate_proof(&proof),
+            Err(FHEError::InvalidProof)
+        ));
+    }
+
+    #[test]
+    fn test_scalar_byte() {
+        assert_eq!(scalar_byte(SCALAR_ENCRYPTED), FixedBytes([0x00]));
+        assert_eq!(scalar_byte(SCALAR_PLAIN), FixedBytes([0x01]));
+    }
+
+    #[test]
+    fn test_plain_operand_is_big_endian() {
+        let encoded = plain_operand(U256::from(0x0102));
+        assert_eq!(encoded[30], 0x01);
+        assert_eq!(encoded[31], 0x02);
+        assert!(encoded[..30].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_binary_operands_encrypted_encrypted() {
+        let lhs = FixedBytes::repeat_byte(0x01);
+        let rhs = FixedBytes::repeat_byte(0x02);
+
+        let (a, b, scalar) = binary_operands(lhs.into(), rhs.into(), false).unwrap();
+        assert_eq!((a, b), (lhs, rhs));
+        assert_eq!(scalar, scalar_byte(SCALAR_ENCRYPTED));
+    }
+
+    #[test]
+    fn test_binary_operands_encrypted_plain() {
+        let handle = FixedBytes::repeat_byte(0x01);
+        let value = U256::from(10);
+
+        let (a, b, scalar) = binary_operands(handle.into(), value.into(), false).unwrap();
+        assert_eq!((a, b), (handle, plain_operand(value)));
+        assert_eq!(scalar, scalar_byte(SCALAR_PLAIN));
+
+        // Commutative operations move a plaintext lhs to the right
+        let (a, b, scalar) = binary_operands(value.into(), handle.into(), true).unwrap();
+        assert_eq!((a, b), (handle, plain_operand(value)));
+        assert_eq!(scalar, scalar_byte(SCALAR_PLAIN));
+
+        // Non-commutative operations cannot
+        assert!(matches!(
+            binary_operands(value.into(), handle.into(), false),
+            Err(FHEError::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_binary_operands_plain_plain_rejected() {
+        let result = binary_operands(U256::from(1).into(), U256::from(2).into(), true);
+        assert!(matches!(result, Err(FHEError::InvalidInput)));
+    }
+
+    #[test]
+    fn test_rand_bounded_args() {
+        use crate::interfaces::{EUINT256_TYPE, EUINT64_TYPE, EUINT8_TYPE};
+
+        assert!(rand_bounded_args(U256::from(1), EUINT8_TYPE).is_ok());
+        assert!(rand_bounded_args(U256::from(8), EUINT8_TYPE).is_ok());
+        assert!(rand_bounded_args(U256::from(256), EUINT8_TYPE).is_ok());
+        assert!(rand_bounded_args(U256::from(1u128 << 64), EUINT64_TYPE).is_ok());
+        assert!(rand_bounded_args(U256::from(1) << 255, EUINT256_TYPE).is_ok());
+
+        // Zero upper bound would make `[0, upper_bound)` empty
+        assert!(matches!(
+            rand_bounded_args(U256::ZERO, EUINT64_TYPE),
+            Err(FHEError::InvalidInput)
+        ));
+
+        // fheRandBounded only takes power-of-two bounds
+        assert!(matches!(
+            rand_bounded_args(U256::from(6), EUINT8_TYPE),
+            Err(FHEError::InvalidInput)
+        ));
+        assert!(matches!(
+            rand_bounded_args(U256::MAX, EUINT256_TYPE),
+            Err(FHEError::InvalidInput)
+        ));
+
+        // Bound wider than the type
+        assert!(matches!(
+            rand_bounded_args(U256::from(512), EUINT8_TYPE),
+            Err(FHEError::InvalidInput)
+        ));
+
+        // Unknown type byte
+        assert!(matches!(
+            rand_bounded_args(U256::from(8), 0xff),
+            Err(FHEError::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_rand_bounded_args_rejects_non_integer_types() {
+        use crate::interfaces::{EADDRESS_TYPE, EBOOL_TYPE};
+
+        assert!(matches!(
+            rand_bounded_args(U256::from(2), EBOOL_TYPE),
+            Err(FHEError::InvalidInput)
+        ));
+        assert!(matches!(
+            rand_bounded_args(U256::from(2), EADDRESS_TYPE),
+            Err(FHEError::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_rand_bounded_args_forwarded() {
+        use crate::interfaces::EUINT16_TYPE;
+        use stylus_sdk::alloy_sol_types::{sol_data, SolType};
+
+        let args = rand_bounded_args(U256::from(1024), EUINT16_TYPE).unwrap();
+        assert_eq!(args, (U256::from(1024), EUINT16_TYPE));
+
+        // `fheRandBounded(uint256 upperBound, uint8 randType)` parameters, as
+        // `IFHEVMPrecompile::fhe_rand_bounded` encodes them
+        let encoded = <(sol_data::Uint<256>, sol_data::Uint<8>)>::abi_encode_params(&args);
+        assert_eq!(encoded.len(), 64);
+        assert_eq!(encoded[..32], U256::from(1024).to_be_bytes::<32>());
+        assert!(encoded[32..63].iter().all(|b| *b == 0));
+        assert_eq!(encoded[63], EUINT16_TYPE);
+    }
+
+    #[test]
+    fn test_rand_args() {
+        use crate::interfaces::{EADDRESS_TYPE, EBOOL_TYPE, EUINT256_TYPE, EUINT8_TYPE};
+
+        assert!(rand_args(EUINT8_TYPE).is_ok());
+        assert!(rand_args(EUINT256_TYPE).is_ok());
+        assert_eq!(rand_args(EBOOL_TYPE).unwrap(), EBOOL_TYPE);
+        assert!(matches!(
+            rand_args(EADDRESS_TYPE),
+            Err(FHEError::InvalidInput)
+        ));
+        assert!(matches!(rand_args(0xff), Err(FHEError::InvalidInput)));
+    }
+
+    #[test]
+    fn test_with_config() {
+        let config = FHEVMConfig::from_parts(
+            Address::repeat_byte(0x01),
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x04),
+            Address::repeat_byte(0x05),
+        );
+        let fhe = FHE::with_config(config);
+        assert_eq!(
+            fhe.config().precompile_address(),
+            Address::repeat_byte(0x01)
+        );
+        assert_eq!(
+            fhe.config().input_verifier_address(),
+            Address::repeat_byte(0x02)
+        );
+
+        // The associated functions use the compile-time configuration
+        assert_eq!(
+            FHE::configured().config().precompile_address(),
+            get_config().precompile_address()
+        );
+    }
+
+    #[test]
+    fn test_validate_proof_bounds() {
+        assert!(FHE::validate_proof(&[0u8; MIN_PROOF_LEN]).is_ok());
+        assert!(FHE::validate_proof(&vec![0u8; MAX_PROOF_LEN]).is_ok());
+    }
+}
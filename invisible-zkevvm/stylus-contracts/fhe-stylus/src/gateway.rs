@@ -0,0 +1,362 @@
+//! Gateway Decryption Helpers
+//!
+//! Thin wrappers around the `IGateway` contract for the Zama FHEVM path.
+//! The [`Gateway`] functions use the gateway address from [`get_config`];
+//! [`Gateway::with_config`] uses one chosen at deploy time instead.
+//!
+//! # Decryption Pattern
+//! Decryption is asynchronous: the KMS threshold network decrypts off-chain,
+//! so a contract cannot read the plaintext in the same transaction.
+//!
+//! 1. Call [`Gateway::request_decryption`] and store the returned request ID
+//! 2. In a later transaction, check [`Gateway::is_ready`] for that ID
+//! 3. Once ready, read the plaintext with [`Gateway::get_value`]
+//!
+//! ```ignore
+//! use fhe_stylus::gateway::Gateway;
+//!
+//! // Transaction 1
+//! let request_id = Gateway::request_decryption(handle, msg::sender(), Call::new_in(self))
+//!     .map_err(|_| b"Decryption request failed".to_vec())?;
+//! self.pending.insert(request_id, handle);
+//!
+//! // Transaction 2
+//! if !Gateway::is_ready(request_id, Call::new_in(self)).map_err(|_| b"Gateway call failed".to_vec())? {
+//!     return Err(b"Decryption not ready".to_vec());
+//! }
+//! let value = Gateway::get_value(request_id, Call::new_in(self))
+//!     .map_err(|_| b"Gateway call failed".to_vec())?;
+//! ```
+//!
+//! # Tracking Requests
+//! A contract that stores its request IDs should complete each one with
+//! [`complete_request`], which removes it so the same request cannot be
+//! finalized twice. It works on any [`DecryptionRequests`] store, including
+//! the [`PendingDecryptions`] storage type:
+//!
+//! ```ignore
+//! use fhe_stylus::gateway::{complete_request, require_pending, Gateway};
+//!
+//! self.pending.insert(request_id, handle);
+//!
+//! // Later
+//! require_pending(&self.pending, request_id).map_err(|_| b"Unknown request".to_vec())?;
+//! let ready = Gateway::is_ready(request_id, Call::new_in(self))
+//!     .map_err(|_| b"Gateway call failed".to_vec())?;
+//! complete_request(&mut self.pending, request_id, ready)
+//!     .map_err(|_| b"Decryption not ready".to_vec())?;
+//! let value = Gateway::get_value(request_id, Call::new_in(self))
+//!     .map_err(|_| b"Gateway call failed".to_vec())?;
+//! ```
+
+use stylus_sdk::alloy_primitives::{Address, U256};
+use stylus_sdk::call::{NonPayableCallContext, StaticCallContext};
+use stylus_sdk::prelude::*;
+use stylus_sdk::storage::{StorageB256, StorageBool, StorageMap};
+
+use crate::config::{get_config, FHEVMConfig};
+use crate::fhe::FHEError;
+use crate::interfaces::IGateway;
+use crate::types::Euint64;
+
+/// Gateway decryption helpers
+pub struct Gateway;
+
+impl Gateway {
+    /// Get the Gateway address for the current network
+    pub const fn address() -> Address {
+        get_config().gateway_address()
+    }
+
+    /// Gateway calls against an explicit configuration instead of [`get_config`]
+    ///
+    /// See [`ConfiguredGateway`].
+    pub fn with_config(config: FHEVMConfig) -> ConfiguredGateway {
+        ConfiguredGateway { config }
+    }
+
+    /// Gateway calls against the compile-time configuration
+    fn configured() -> ConfiguredGateway {
+        Self::with_config(get_config())
+    }
+
+    /// Request decryption of an encrypted value
+    ///
+    /// # Parameters
+    /// * `handle` - Handle to the encrypted value to decrypt
+    /// * `user` - Address requesting the decryption (must be allowed on the handle)
+    /// * `call_ctx` - Call context, e.g. `Call::new_in(self)`
+    ///
+    /// # Returns
+    /// * `Result<U256, FHEError>` - Request ID for polling the result
+    pub fn request_decryption(
+        handle: Euint64,
+        user: Address,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<U256, FHEError> {
+        Self::configured().request_decryption(handle, user, call_ctx)
+    }
+
+    /// Check whether a decryption request has completed
+    pub fn is_ready(request_id: U256, call_ctx: impl StaticCallContext) -> Result<bool, FHEError> {
+        Self::configured().is_ready(request_id, call_ctx)
+    }
+
+    /// Get the decrypted value of a completed request
+    ///
+    /// Check [`Gateway::is_ready`] first: the gateway reverts for pending requests.
+    pub fn get_value(request_id: U256, call_ctx: impl StaticCallContext) -> Result<U256, FHEError> {
+        Self::configured().get_value(request_id, call_ctx)
+    }
+}
+
+/// Gateway calls against an explicit [`FHEVMConfig`]
+///
+/// The associated functions on [`Gateway`] use the compile-time [`get_config`].
+/// Build one of these with [`Gateway::with_config`] to use the gateway chosen
+/// at deploy time instead, e.g. from a [`StoredFHEVMConfig`](crate::config::StoredFHEVMConfig):
+///
+/// ```ignore
+/// let gateway = Gateway::with_config(self.fhevm.load());
+/// let request_id = gateway.request_decryption(handle, contract::address(), Call::new_in(self))?;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConfiguredGateway {
+    config: FHEVMConfig,
+}
+
+impl ConfiguredGateway {
+    /// The configuration these calls use
+    pub fn config(&self) -> &FHEVMConfig {
+        &self.config
+    }
+
+    /// The Gateway address these calls go to
+    pub fn address(&self) -> Address {
+        self.config.gateway_address()
+    }
+
+    /// See [`Gateway::request_decryption`]
+    pub fn request_decryption(
+        &self,
+        handle: Euint64,
+        user: Address,
+        call_ctx: impl NonPayableCallContext,
+    ) -> Result<U256, FHEError> {
+        IGateway::new(self.address())
+            .request_decryption(call_ctx, handle, user)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`Gateway::is_ready`]
+    pub fn is_ready(
+        &self,
+        request_id: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<bool, FHEError> {
+        IGateway::new(self.address())
+            .is_decryption_ready(call_ctx, request_id)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+
+    /// See [`Gateway::get_value`]
+    pub fn get_value(
+        &self,
+        request_id: U256,
+        call_ctx: impl StaticCallContext,
+    ) -> Result<U256, FHEError> {
+        IGateway::new(self.address())
+            .get_decrypted_value(call_ctx, request_id)
+            .map_err(|_| FHEError::PrecompileCallFailed)
+    }
+}
+
+/// Errors returned when completing a tracked decryption request
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecryptionError {
+    /// The request ID is not pending: never issued, or already completed
+    UnknownRequest,
+    /// The gateway has not finished decrypting yet
+    NotReady,
+}
+
+/// Store of pending decryption requests, keyed by gateway request ID
+pub trait DecryptionRequests {
+    /// Handle being decrypted for `request_id`, or `None` if not pending
+    fn pending_handle(&self, request_id: U256) -> Option<Euint64>;
+
+    /// Stop tracking `request_id`
+    fn remove(&mut self, request_id: U256);
+}
+
+/// Pending decryption requests, keyed by gateway request ID
+///
+/// Keeps an explicit pending flag next to each handle, like
+/// [`EncryptedSlot`](crate::storage::EncryptedSlot): the zero handle is a
+/// valid handle, so it cannot double as the "not pending" marker.
+#[storage]
+pub struct PendingDecryptions {
+    handles: StorageMap<U256, StorageB256>,
+    is_pending: StorageMap<U256, StorageBool>,
+}
+
+impl PendingDecryptions {
+    /// Track `request_id` as pending for `handle`
+    pub fn insert(&mut self, request_id: U256, handle: Euint64) {
+        self.handles.insert(request_id, handle);
+        self.is_pending.insert(request_id, true);
+    }
+}
+
+impl DecryptionRequests for PendingDecryptions {
+    fn pending_handle(&self, request_id: U256) -> Option<Euint64> {
+        self.is_pending
+            .get(request_id)
+            .then(|| self.handles.get(request_id))
+    }
+
+    fn remove(&mut self, request_id: U256) {
+        self.handles.delete(request_id);
+        self.is_pending.delete(request_id);
+    }
+}
+
+/// Fail unless `request_id` is pending in `requests`
+///
+/// # Errors
+/// * `DecryptionError::UnknownRequest` - `request_id` is not pending
+pub fn require_pending<S: DecryptionRequests>(
+    requests: &S,
+    request_id: U256,
+) -> Result<Euint64, DecryptionError> {
+    requests
+        .pending_handle(request_id)
+        .ok_or(DecryptionError::UnknownRequest)
+}
+
+/// Complete a pending request once the gateway reports it ready
+///
+/// Removes `request_id` from `requests`, so a second call fails with
+/// `UnknownRequest`. A request that is not ready stays pending.
+///
+/// # Parameters
+/// * `requests` - Pending requests of the calling contract
+/// * `request_id` - Gateway request ID
+/// * `ready` - Result of [`Gateway::is_ready`] for `request_id`
+///
+/// # Returns
+/// * `Result<Euint64, DecryptionError>` - The handle that was decrypted
+///
+/// # Errors
+/// * `DecryptionError::UnknownRequest` - `request_id` is not pending
+/// * `DecryptionError::NotReady` - `ready` is false
+pub fn complete_request<S: DecryptionRequests>(
+    requests: &mut S,
+    request_id: U256,
+    ready: bool,
+) -> Result<Euint64, DecryptionError> {
+    let handle = require_pending(requests, request_id)?;
+    if !ready {
+        return Err(DecryptionError::NotReady);
+    }
+
+    requests.remove(request_id);
+    Ok(handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeMap;
+    use stylus_sdk::alloy_primitives::B256;
+
+    impl DecryptionRequests for BTreeMap<U256, Euint64> {
+        fn pending_handle(&self, request_id: U256) -> Option<Euint64> {
+            self.get(&request_id).copied()
+        }
+
+        fn remove(&mut self, request_id: U256) {
+            BTreeMap::remove(self, &request_id);
+        }
+    }
+
+    #[test]
+    fn test_gateway_address_from_config() {
+        assert_eq!(Gateway::address(), get_config().gateway);
+    }
+
+    #[test]
+    fn test_gateway_with_config() {
+        let config = FHEVMConfig::from_parts(
+            Address::repeat_byte(0x01),
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x04),
+            Address::repeat_byte(0x05),
+        );
+        let gateway = Gateway::with_config(config);
+        assert_eq!(gateway.address(), Address::repeat_byte(0x04));
+        assert_eq!(gateway.config().gateway_address(), config.gateway);
+
+        assert_eq!(Gateway::configured().address(), Gateway::address());
+    }
+
+    #[test]
+    fn test_complete_request_once() {
+        let handle = B256::repeat_byte(0x11);
+        let request_id = U256::from(1);
+        let mut requests = BTreeMap::new();
+        requests.insert(request_id, handle);
+
+        assert_eq!(require_pending(&requests, request_id), Ok(handle));
+
+        // Pending requests stay tracked until ready
+        assert_eq!(
+            complete_request(&mut requests, request_id, false),
+            Err(DecryptionError::NotReady)
+        );
+        assert_eq!(require_pending(&requests, request_id), Ok(handle));
+
+        assert_eq!(complete_request(&mut requests, request_id, true), Ok(handle));
+
+        // A second finalize is rejected
+        assert_eq!(
+            complete_request(&mut requests, request_id, true),
+            Err(DecryptionError::UnknownRequest)
+        );
+        assert_eq!(
+            require_pending(&requests, request_id),
+            Err(DecryptionError::UnknownRequest)
+        );
+    }
+
+    #[test]
+    fn test_complete_request_zero_handle() {
+        // The zero handle is a real handle, not a "not pending" marker
+        let request_id = U256::from(1);
+        let mut requests = BTreeMap::new();
+        requests.insert(request_id, B256::ZERO);
+
+        assert_eq!(require_pending(&requests, request_id), Ok(B256::ZERO));
+        assert_eq!(
+            complete_request(&mut requests, request_id, true),
+            Ok(B256::ZERO)
+        );
+        assert_eq!(
+            require_pending(&requests, request_id),
+            Err(DecryptionError::UnknownRequest)
+        );
+    }
+
+    #[test]
+    fn test_complete_request_unknown() {
+        let mut requests = BTreeMap::new();
+        requests.insert(U256::from(1), B256::repeat_byte(0x11));
+
+        assert_eq!(
+            complete_request(&mut requests, U256::from(2), true),
+            Err(DecryptionError::UnknownRequest)
+        );
+        assert_eq!(requests.len(), 1);
+    }
+}
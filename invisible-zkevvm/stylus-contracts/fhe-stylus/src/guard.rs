@@ -0,0 +1,330 @@
+//! Contract Guards
+//!
+//! Pure checks shared by contracts built on this library. They take plain
+//! values read from storage, so they work with any storage layout.
+//!
+//! # Initialization Guard
+//! Stylus contracts have no constructor: state is set by a public
+//! `initialize` function after deployment. Without a guard, anyone can call
+//! it again and overwrite the owner. The standard pattern is an
+//! `initialized: StorageBool` checked with [`require_not_initialized`] and set
+//! in the same call:
+//!
+//! ```ignore
+//! use fhe_stylus::guard::require_not_initialized;
+//!
+//! pub fn initialize(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+//!     require_not_initialized(self.initialized.get())
+//!         .map_err(|_| b"Already initialized".to_vec())?;
+//!     self.initialized.set(true);
+//!
+//!     self.owner.set(owner);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! # Owner Guard
+//! Put [`require_owner`] on the first line of every privileged method, the
+//! Stylus equivalent of Solidity's `onlyOwner` modifier:
+//!
+//! ```ignore
+//! use fhe_stylus::guard::require_owner;
+//!
+//! pub fn set_fee(&mut self, fee: U256) -> Result<(), Vec<u8>> {
+//!     require_owner(msg::sender(), self.owner.get())
+//!         .map_err(|_| b"Unauthorized".to_vec())?;
+//!
+//!     self.fee.set(fee);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! # Two-Step Ownership Transfer
+//! [`OwnershipTransfer`] holds the owner and the proposed owner. The owner
+//! proposes, and ownership only moves when the proposed address accepts, so
+//! a typo cannot hand the contract to an address nobody controls:
+//!
+//! ```ignore
+//! use fhe_stylus::guard::OwnershipTransfer;
+//!
+//! pub fn accept_ownership(&mut self) -> Result<(), Vec<u8>> {
+//!     let mut transfer = OwnershipTransfer::new(self.owner.get(), self.pending_owner.get());
+//!     transfer.accept(msg::sender()).map_err(|_| b"Not pending owner".to_vec())?;
+//!
+//!     self.owner.set(transfer.owner());
+//!     self.pending_owner.set(transfer.pending_owner());
+//!     Ok(())
+//! }
+//! ```
+
+use stylus_sdk::alloy_primitives::Address;
+
+/// Errors returned by the guards in this module
+#[derive(Debug, PartialEq, Eq)]
+pub enum GuardError {
+    /// `initialize` was already called
+    AlreadyInitialized,
+    /// Caller is not the owner
+    Unauthorized,
+    /// Address is zero or has no deployed code
+    NotAContract,
+    /// A guarded function was entered again before it returned
+    Reentrant,
+    /// Caller is not the proposed owner, or no transfer is pending
+    NotPendingOwner,
+}
+
+/// Fail if the contract has already been initialized
+///
+/// # Parameters
+/// * `initialized` - Current value of the contract's `initialized` flag
+///
+/// # Errors
+/// * `GuardError::AlreadyInitialized` - `initialized` is true
+pub fn require_not_initialized(initialized: bool) -> Result<(), GuardError> {
+    if initialized {
+        return Err(GuardError::AlreadyInitialized);
+    }
+    Ok(())
+}
+
+/// Fail unless `caller` is `owner`
+///
+/// # Parameters
+/// * `caller` - Usually `msg::sender()`
+/// * `owner` - The stored owner address
+///
+/// # Errors
+/// * `GuardError::Unauthorized` - `caller` differs from `owner`
+pub fn require_owner(caller: Address, owner: Address) -> Result<(), GuardError> {
+    if caller != owner {
+        return Err(GuardError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Fail if staker status is `required` and the caller is not a staker
+///
+/// `is_staker` usually makes an external call (e.g.
+/// `IEVVMCore::is_address_staker`), so it is only run when `required` is
+/// true. Its error is passed through unchanged.
+///
+/// # Parameters
+/// * `required` - Whether the contract currently requires staker status
+/// * `is_staker` - Looks up the caller's staker status
+/// * `not_staker` - Error returned when the caller is not a staker
+pub fn require_staker<E>(
+    required: bool,
+    is_staker: impl FnOnce() -> Result<bool, E>,
+    not_staker: E,
+) -> Result<(), E> {
+    if required && !is_staker()? {
+        return Err(not_staker);
+    }
+    Ok(())
+}
+
+/// Fail if a guarded function is already running
+///
+/// Only needed when the contract is built with stylus-sdk's `reentrant`
+/// feature: without it, the SDK already reverts any reentrant call.
+///
+/// # Parameters
+/// * `entered` - Current value of the contract's reentrancy flag
+///
+/// # Errors
+/// * `GuardError::Reentrant` - `entered` is true
+pub fn require_not_entered(entered: bool) -> Result<(), GuardError> {
+    if entered {
+        return Err(GuardError::Reentrant);
+    }
+    Ok(())
+}
+
+/// Fail unless `target` is non-zero and has deployed code
+///
+/// Use when storing the address of a contract that will be called later,
+/// e.g. in `initialize`, so a typo fails immediately rather than on every
+/// later call.
+///
+/// # Parameters
+/// * `target` - Address to check
+/// * `has_code` - `target.has_code()` (from `stylus_sdk::types::AddressVM`)
+///
+/// # Errors
+/// * `GuardError::NotAContract` - `target` is zero or `has_code` is false
+pub fn require_contract(target: Address, has_code: bool) -> Result<(), GuardError> {
+    if target == Address::ZERO || !has_code {
+        return Err(GuardError::NotAContract);
+    }
+    Ok(())
+}
+
+/// Owner and proposed owner of a contract, for two-step ownership transfer
+///
+/// Load it from storage, apply [`propose`](Self::propose) or
+/// [`accept`](Self::accept), then write both addresses back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnershipTransfer {
+    owner: Address,
+    pending_owner: Address,
+}
+
+impl OwnershipTransfer {
+    /// Create from the stored owner and pending owner (zero if none)
+    pub fn new(owner: Address, pending_owner: Address) -> Self {
+        Self { owner, pending_owner }
+    }
+
+    /// Current owner
+    pub fn owner(&self) -> Address {
+        self.owner
+    }
+
+    /// Proposed owner, or zero if no transfer is pending
+    pub fn pending_owner(&self) -> Address {
+        self.pending_owner
+    }
+
+    /// Propose `new_owner` (step 1 of 2)
+    ///
+    /// Proposing `Address::ZERO` cancels a pending transfer.
+    ///
+    /// # Errors
+    /// * `GuardError::Unauthorized` - `caller` is not the owner
+    pub fn propose(&mut self, caller: Address, new_owner: Address) -> Result<(), GuardError> {
+        require_owner(caller, self.owner)?;
+        self.pending_owner = new_owner;
+        Ok(())
+    }
+
+    /// Accept a pending transfer (step 2 of 2)
+    ///
+    /// # Returns
+    /// * `Result<Address, GuardError>` - The previous owner
+    ///
+    /// # Errors
+    /// * `GuardError::NotPendingOwner` - No transfer is pending, or `caller` is not the proposed owner
+    pub fn accept(&mut self, caller: Address) -> Result<Address, GuardError> {
+        if self.pending_owner == Address::ZERO || caller != self.pending_owner {
+            return Err(GuardError::NotPendingOwner);
+        }
+
+        let previous_owner = self.owner;
+        self.owner = self.pending_owner;
+        self.pending_owner = Address::ZERO;
+        Ok(previous_owner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_not_initialized() {
+        // First initialize succeeds
+        assert!(require_not_initialized(false).is_ok());
+
+        // Second initialize is rejected
+        assert_eq!(require_not_initialized(true), Err(GuardError::AlreadyInitialized));
+    }
+
+    #[test]
+    fn test_require_owner() {
+        let owner = Address::repeat_byte(0x01);
+        assert!(require_owner(owner, owner).is_ok());
+        assert_eq!(
+            require_owner(Address::repeat_byte(0x02), owner),
+            Err(GuardError::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_require_contract() {
+        assert_eq!(require_contract(Address::ZERO, false), Err(GuardError::NotAContract));
+
+        // Code-less account, e.g. a typo in the address
+        assert_eq!(
+            require_contract(Address::repeat_byte(0x11), false),
+            Err(GuardError::NotAContract)
+        );
+
+        assert!(require_contract(Address::repeat_byte(0x11), true).is_ok());
+    }
+
+    #[test]
+    fn test_require_not_entered() {
+        // First entry passes
+        assert!(require_not_entered(false).is_ok());
+
+        // A nested call sees the flag still set
+        assert_eq!(require_not_entered(true), Err(GuardError::Reentrant));
+    }
+
+    #[test]
+    fn test_require_staker() {
+        // Not required: the lookup is skipped entirely
+        assert_eq!(require_staker(false, || -> Result<bool, u8> { panic!("lookup") }, 1), Ok(()));
+
+        assert_eq!(require_staker(true, || Ok::<_, u8>(true), 1), Ok(()));
+        assert_eq!(require_staker(true, || Ok::<_, u8>(false), 1), Err(1));
+
+        // Lookup failures are passed through
+        assert_eq!(require_staker(true, || Err::<bool, u8>(2), 1), Err(2));
+    }
+
+    #[test]
+    fn test_ownership_transfer_two_step() {
+        let owner = Address::repeat_byte(0x01);
+        let new_owner = Address::repeat_byte(0x02);
+        let mut transfer = OwnershipTransfer::new(owner, Address::ZERO);
+
+        transfer.propose(owner, new_owner).unwrap();
+
+        // Proposing does not move ownership
+        assert_eq!(transfer.owner(), owner);
+        assert_eq!(transfer.pending_owner(), new_owner);
+
+        assert_eq!(transfer.accept(new_owner), Ok(owner));
+        assert_eq!(transfer.owner(), new_owner);
+        assert_eq!(transfer.pending_owner(), Address::ZERO);
+
+        // The previous owner lost its rights
+        assert_eq!(transfer.propose(owner, owner), Err(GuardError::Unauthorized));
+    }
+
+    #[test]
+    fn test_ownership_transfer_unauthorized_proposer() {
+        let owner = Address::repeat_byte(0x01);
+        let mut transfer = OwnershipTransfer::new(owner, Address::ZERO);
+
+        assert_eq!(
+            transfer.propose(Address::repeat_byte(0x03), Address::repeat_byte(0x03)),
+            Err(GuardError::Unauthorized)
+        );
+        assert_eq!(transfer, OwnershipTransfer::new(owner, Address::ZERO));
+    }
+
+    #[test]
+    fn test_ownership_transfer_wrong_acceptor() {
+        let owner = Address::repeat_byte(0x01);
+        let new_owner = Address::repeat_byte(0x02);
+        let mut transfer = OwnershipTransfer::new(owner, Address::ZERO);
+
+        // Nothing pending
+        assert_eq!(transfer.accept(new_owner), Err(GuardError::NotPendingOwner));
+
+        transfer.propose(owner, new_owner).unwrap();
+        assert_eq!(
+            transfer.accept(Address::repeat_byte(0x03)),
+            Err(GuardError::NotPendingOwner)
+        );
+        assert_eq!(transfer.owner(), owner);
+
+        // Cancelled transfers cannot be accepted
+        transfer.propose(owner, Address::ZERO).unwrap();
+        assert_eq!(transfer.accept(new_owner), Err(GuardError::NotPendingOwner));
+        assert_eq!(transfer.accept(Address::ZERO), Err(GuardError::NotPendingOwner));
+    }
+}
@@ -17,6 +17,8 @@
 use stylus_sdk::prelude::*;
 use stylus_sdk::alloy_sol_types;
 
+use crate::types::EncryptedType;
+
 // Define the Solidity interface for FHEVM Input Verifier
 sol_interface! {
     /// Input Verifier Precompile
@@ -376,18 +378,47 @@ sol_interface! {
     }
 }
 
-// Type constants for input verification
-pub const EUINT8_TYPE: u8 = 0;
-pub const EUINT16_TYPE: u8 = 1;
-pub const EUINT32_TYPE: u8 = 2;
-pub const EUINT64_TYPE: u8 = 3;
-pub const EUINT128_TYPE: u8 = 4;
-pub const EUINT256_TYPE: u8 = 5;
-pub const EBOOL_TYPE: u8 = 6;
-pub const EADDRESS_TYPE: u8 = 7;
+// Type constants for input verification, numbered per the selected FHEVM
+// version (see `zama_types`)
+pub use crate::zama_types::selected::{
+    EADDRESS_TYPE, EBOOL_TYPE, EUINT128_TYPE, EUINT16_TYPE, EUINT256_TYPE, EUINT32_TYPE,
+    EUINT64_TYPE, EUINT8_TYPE,
+};
+
+/// Check whether a byte is one of the known input type constants
+pub const fn is_known_input_type(input_type: u8) -> bool {
+    EncryptedType::from_zama_u8(input_type).is_some()
+}
 
 // Scalar byte constants
 /// Indicates both operands are encrypted
 pub const SCALAR_ENCRYPTED: u8 = 0x00;
 /// Indicates the right operand is a plaintext scalar
 pub const SCALAR_PLAIN: u8 = 0x01;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_known_input_type() {
+        let known = [
+            EUINT8_TYPE,
+            EUINT16_TYPE,
+            EUINT32_TYPE,
+            EUINT64_TYPE,
+            EUINT128_TYPE,
+            EUINT256_TYPE,
+            EBOOL_TYPE,
+            EADDRESS_TYPE,
+        ];
+        for input_type in known {
+            assert!(is_known_input_type(input_type));
+        }
+
+        // Every other byte is rejected, including `Uint4 = 1` under v0.7
+        for input_type in (0..=u8::MAX).filter(|b| !known.contains(b)) {
+            assert!(!is_known_input_type(input_type), "accepted {input_type}");
+        }
+    }
+}
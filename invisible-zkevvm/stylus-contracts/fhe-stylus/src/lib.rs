@@ -44,7 +44,7 @@
 //!         proof: Vec<u8>
 //!     ) -> Result<(), Vec<u8>> {
 //!         // Verify encrypted input
-//!         let verified_amount = FHE::from_external(amount, &proof)
+//!         let verified_amount = FHE::from_external_u64(amount, &proof, Call::new_in(self))
 //!             .map_err(|_| b"Invalid input".to_vec())?;
 //!
 //!         // Get encrypted balances
@@ -52,9 +52,9 @@
 //!         let receiver_balance = self.balances.get(to);
 //!
 //!         // Perform encrypted arithmetic
-//!         let new_sender = FHE::sub(sender_balance, verified_amount)
+//!         let new_sender = FHE::sub(sender_balance.into(), verified_amount.into(), Call::new_in(self))
 //!             .map_err(|_| b"Insufficient balance".to_vec())?;
-//!         let new_receiver = FHE::add(receiver_balance, verified_amount)
+//!         let new_receiver = FHE::add(receiver_balance.into(), verified_amount.into(), Call::new_in(self))
 //!             .map_err(|_| b"Overflow".to_vec())?;
 //!
 //!         // Update state
@@ -62,9 +62,9 @@
 //!         self.balances.insert(to, new_receiver);
 //!
 //!         // Grant access for decryption
-//!         FHE::allow(new_sender, msg::sender())
+//!         FHE::allow(new_sender, msg::sender(), Call::new_in(self))
 //!             .map_err(|_| b"Access control failed".to_vec())?;
-//!         FHE::allow(new_receiver, to)
+//!         FHE::allow(new_receiver, to, Call::new_in(self))
 //!             .map_err(|_| b"Access control failed".to_vec())?;
 //!
 //!         Ok(())
@@ -86,9 +86,13 @@
 //! - `arbitrum-mainnet` - Arbitrum mainnet (coming soon)
 //! - `arbitrum-testnet` - Arbitrum testnet
 //!
+//! The `fhevm-v0-7` feature switches the encrypted type bytes to the FHEVM
+//! v0.7 numbering (see [`zama_types`]).
+//!
 //! ## Security Considerations
 //!
-//! 1. **Always verify external inputs** with `FHE::from_external()` and proofs
+//! 1. **Always verify external inputs** with `FHE::from_external_u64()` (or the
+//!    helper for your width) and their proofs
 //! 2. **Grant access carefully** - only allow decryption to authorized addresses
 //! 3. **Handle errors** - FHE operations can fail, always use proper error handling
 //! 4. **Gas costs** - FHE operations are more expensive than plaintext operations
@@ -108,16 +112,35 @@ pub extern crate stylus_sdk;
 
 // Module declarations
 pub mod config;
+pub mod convert;
 pub mod fhe;
+pub mod gateway;
+pub mod guard;
 pub mod interfaces;
+pub mod nonce;
+pub mod order;
+pub mod revert;
 pub mod signature;
+pub mod storage;
+
+// Revert-on-panic handler (opt-in, WASM only)
+#[cfg(all(feature = "panic-revert", target_arch = "wasm32", not(test)))]
+mod panic;
 pub mod types;
+pub mod zama_types;
 
 // Re-export main types and functions for convenience
-pub use config::{get_config, FHEVMConfig};
-pub use fhe::{FHEError, FHE};
-pub use signature::{SignatureError, SignatureRecover};
-pub use types::{Ebool, Euint256, Euint64, ExternalEuint256, ExternalEuint64};
+pub use config::{config_fingerprint, get_config, ConfigError, FHEVMConfig, StoredFHEVMConfig};
+pub use fhe::{ConfiguredFHE, FHEError, Operand, FHE};
+pub use gateway::{ConfiguredGateway, Gateway, PendingDecryptions};
+pub use guard::GuardError;
+pub use order::OrderError;
+pub use signature::{EvvmMessage, SignatureError, SignatureRecover};
+pub use storage::EncryptedSlot;
+pub use types::{
+    EncryptedType, Ebool, Euint128, Euint16, Euint256, Euint32, Euint64, Euint8, ExternalEuint128,
+    ExternalEuint16, ExternalEuint256, ExternalEuint32, ExternalEuint64, ExternalEuint8,
+};
 
 // Re-export commonly used Stylus types
 pub use stylus_sdk::prelude::*;
@@ -131,11 +154,33 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// ```ignore
 /// use fhe_stylus::prelude::*;
 /// ```
+///
+/// This includes the Zama precompile interfaces and the type and scalar
+/// byte constants, so calling a precompile needs no further imports:
+/// ```
+/// use fhe_stylus::prelude::*;
+///
+/// let _precompile = IFHEVMPrecompile::new(get_config().precompile_address());
+/// let _verifier = IInputVerifier::new(get_config().input_verifier_address());
+///
+/// assert_eq!(EncryptedType::from_zama_u8(EUINT64_TYPE), Some(EncryptedType::Uint64));
+/// assert_eq!(SCALAR_ENCRYPTED, 0x00);
+/// assert_eq!(SCALAR_PLAIN, 0x01);
+/// ```
 pub mod prelude {
-    pub use crate::fhe::{FHEError, FHE};
-    pub use crate::types::{Ebool, Euint256, Euint64, ExternalEuint256, ExternalEuint64};
-    pub use crate::signature::{SignatureError, SignatureRecover};
+    pub use crate::fhe::{ConfiguredFHE, FHEError, Operand, FHE};
+    pub use crate::gateway::{ConfiguredGateway, Gateway};
+    pub use crate::types::{
+        EncryptedType, Ebool, Euint128, Euint16, Euint256, Euint32, Euint64, Euint8, ExternalEuint128,
+        ExternalEuint16, ExternalEuint256, ExternalEuint32, ExternalEuint64, ExternalEuint8,
+    };
+    pub use crate::signature::{EvvmMessage, SignatureError, SignatureRecover};
     pub use crate::config::get_config;
+    pub use crate::interfaces::{
+        IACL, IFHEPayment, IFHEVMPrecompile, IGateway, IInputVerifier, EADDRESS_TYPE, EBOOL_TYPE,
+        EUINT128_TYPE, EUINT16_TYPE, EUINT256_TYPE, EUINT32_TYPE, EUINT64_TYPE, EUINT8_TYPE,
+        SCALAR_ENCRYPTED, SCALAR_PLAIN,
+    };
     pub use stylus_sdk::prelude::*;
 }
 
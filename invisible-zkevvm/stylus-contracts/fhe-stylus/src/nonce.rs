@@ -0,0 +1,311 @@
+//! Domain-Separated Nonces
+//!
+//! A contract that tracks client nonces in a single map lets a nonce signed
+//! for one function be checked against (and consumed by) another. Hashing the
+//! nonce together with a per-function domain gives each function its own
+//! nonce space in the same map:
+//!
+//! ```text
+//! domain = keccak256(function_name)[..4]
+//! key    = keccak256(domain || nonce)
+//! ```
+//!
+//! ```ignore
+//! use fhe_stylus::nonce::{is_nonce_used, mark_nonce_used, nonce_domain};
+//!
+//! let domain = nonce_domain("orderCoffee");
+//! if is_nonce_used(&*self.used_nonces.getter(client), domain, nonce) {
+//!     return Err(b"Nonce already used".to_vec());
+//! }
+//! mark_nonce_used(&mut *self.used_nonces.setter(client), domain, nonce);
+//! ```
+//!
+//! # Nonce Stores
+//! The helpers below work on any [`NonceStore`], including the
+//! `StorageMap<U256, StorageBool>` a contract keeps per client. Range
+//! operations are capped at [`MAX_NONCE_RANGE`] nonces, since each nonce is
+//! one storage access:
+//!
+//! ```ignore
+//! use fhe_stylus::nonce::{mark_nonce_range, nonce_domain};
+//!
+//! let mut client_nonces = self.used_nonces.setter(client);
+//! mark_nonce_range(&mut *client_nonces, nonce_domain("orderCoffee"), start, count)
+//!     .map_err(|_| b"Invalid nonce range".to_vec())?;
+//! ```
+//!
+//! # Migration
+//! Contracts that stored nonces before namespacing used the raw nonce value
+//! as the key. Those keys are never written again, but a contract keeping its
+//! storage must still reject them, or previously used nonces (and their
+//! signatures) become valid again. [`is_nonce_used`] therefore checks both
+//! the namespaced and the raw key, at the cost of one extra storage read;
+//! new nonces are only marked under the namespaced key.
+
+use stylus_sdk::alloy_primitives::{keccak256, U256};
+use stylus_sdk::storage::{StorageBool, StorageMap};
+
+use crate::convert::{b256_to_u256, u256_to_b256};
+
+/// 4-byte domain tag for a function name: `keccak256(function_name)[..4]`
+///
+/// Uses the EVVM function name (e.g. `"orderCoffee"`), the same string that
+/// appears in the signed message, not the full ABI signature.
+pub fn nonce_domain(function_name: &str) -> [u8; 4] {
+    let hash = keccak256(function_name.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Storage key for `nonce` within `domain`: `keccak256(domain || nonce)`
+///
+/// The nonce is packed as 32 big-endian bytes.
+pub fn namespaced_nonce(domain: [u8; 4], nonce: U256) -> U256 {
+    let mut packed = [0u8; 36];
+    packed[..4].copy_from_slice(&domain);
+    packed[4..].copy_from_slice(u256_to_b256(nonce).as_slice());
+
+    b256_to_u256(keccak256(packed))
+}
+
+/// Maximum number of nonces checked or marked in one range operation
+pub const MAX_NONCE_RANGE: u64 = 64;
+
+/// Errors returned by the checking helpers in this module
+#[derive(Debug, PartialEq, Eq)]
+pub enum NonceError {
+    /// `count` exceeds `MAX_NONCE_RANGE` or the range overflows `U256`
+    InvalidRange,
+    /// A nonce in the range is already used
+    AlreadyUsed,
+}
+
+/// Used-nonce flags of one client, keyed by storage key
+pub trait NonceStore {
+    /// Whether `key` is marked used
+    fn is_used(&self, key: U256) -> bool;
+
+    /// Mark `key` as used
+    fn mark_used(&mut self, key: U256);
+}
+
+impl NonceStore for StorageMap<U256, StorageBool> {
+    fn is_used(&self, key: U256) -> bool {
+        self.get(key)
+    }
+
+    fn mark_used(&mut self, key: U256) {
+        self.insert(key, true);
+    }
+}
+
+/// Validate a nonce range `[start, start + count)`
+///
+/// # Errors
+/// * `NonceError::InvalidRange` - `count` exceeds `MAX_NONCE_RANGE` or the range overflows `U256`
+pub fn check_nonce_range(start: U256, count: u64) -> Result<(), NonceError> {
+    if count > MAX_NONCE_RANGE || start.checked_add(U256::from(count)).is_none() {
+        return Err(NonceError::InvalidRange);
+    }
+    Ok(())
+}
+
+/// Whether `nonce` is used in `domain`
+///
+/// Also true if `nonce` was marked under its raw key before namespacing
+/// (see the migration note above).
+pub fn is_nonce_used<S: NonceStore>(store: &S, domain: [u8; 4], nonce: U256) -> bool {
+    store.is_used(namespaced_nonce(domain, nonce)) || store.is_used(nonce)
+}
+
+/// Check that `nonce` is unused in `domain` and mark it used
+///
+/// Call this before any external call that the nonce authorizes
+/// (checks-effects-interactions): a call that re-enters with the same nonce
+/// then finds it already used. If the external call fails, the transaction
+/// reverts and the nonce is unmarked with it.
+///
+/// # Errors
+/// * `NonceError::AlreadyUsed` - `nonce` is already used in `domain`
+pub fn consume_nonce<S: NonceStore>(store: &mut S, domain: [u8; 4], nonce: U256) -> Result<(), NonceError> {
+    if is_nonce_used(store, domain, nonce) {
+        return Err(NonceError::AlreadyUsed);
+    }
+    mark_nonce_used(store, domain, nonce);
+    Ok(())
+}
+
+/// Mark `nonce` as used in `domain`
+pub fn mark_nonce_used<S: NonceStore>(store: &mut S, domain: [u8; 4], nonce: U256) {
+    store.mark_used(namespaced_nonce(domain, nonce));
+}
+
+/// Whether every nonce in `[start, start + count)` is unused in `domain`
+///
+/// Returns early on the first used nonce.
+///
+/// # Errors
+/// * `NonceError::InvalidRange` - See [`check_nonce_range`]
+pub fn are_nonces_unused<S: NonceStore>(
+    store: &S,
+    domain: [u8; 4],
+    start: U256,
+    count: u64,
+) -> Result<bool, NonceError> {
+    check_nonce_range(start, count)?;
+
+    for offset in 0..count {
+        if is_nonce_used(store, domain, start + U256::from(offset)) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Mark every nonce in `[start, start + count)` as used in `domain`
+///
+/// The whole range is checked before anything is written: if any nonce is
+/// already used, nothing is marked.
+///
+/// # Errors
+/// * `NonceError::InvalidRange` - See [`check_nonce_range`]
+/// * `NonceError::AlreadyUsed` - A nonce in the range is already used
+pub fn mark_nonce_range<S: NonceStore>(
+    store: &mut S,
+    domain: [u8; 4],
+    start: U256,
+    count: u64,
+) -> Result<(), NonceError> {
+    if !are_nonces_unused(store, domain, start, count)? {
+        return Err(NonceError::AlreadyUsed);
+    }
+
+    for offset in 0..count {
+        mark_nonce_used(store, domain, start + U256::from(offset));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeSet;
+
+    impl NonceStore for BTreeSet<U256> {
+        fn is_used(&self, key: U256) -> bool {
+            self.contains(&key)
+        }
+
+        fn mark_used(&mut self, key: U256) {
+            self.insert(key);
+        }
+    }
+
+    #[test]
+    fn test_nonce_domain() {
+        // keccak256("orderCoffee")[..4]
+        let hash = keccak256("orderCoffee");
+        assert_eq!(nonce_domain("orderCoffee"), [hash[0], hash[1], hash[2], hash[3]]);
+        assert_ne!(nonce_domain("orderCoffee"), nonce_domain("withdrawRewards"));
+    }
+
+    #[test]
+    fn test_same_nonce_separate_domains() {
+        let nonce = U256::from(7);
+        let order = namespaced_nonce(nonce_domain("orderCoffee"), nonce);
+        let other = namespaced_nonce(nonce_domain("refundCoffee"), nonce);
+
+        // Same nonce value, independent keys
+        assert_ne!(order, other);
+        assert_ne!(order, nonce);
+
+        // Deterministic within a domain
+        assert_eq!(order, namespaced_nonce(nonce_domain("orderCoffee"), nonce));
+        assert_ne!(order, namespaced_nonce(nonce_domain("orderCoffee"), U256::from(8)));
+    }
+
+    #[test]
+    fn test_check_nonce_range() {
+        assert!(check_nonce_range(U256::ZERO, MAX_NONCE_RANGE).is_ok());
+        assert_eq!(
+            check_nonce_range(U256::ZERO, MAX_NONCE_RANGE + 1),
+            Err(NonceError::InvalidRange)
+        );
+        assert_eq!(check_nonce_range(U256::MAX, 1), Err(NonceError::InvalidRange));
+        assert!(check_nonce_range(U256::MAX, 0).is_ok());
+    }
+
+    #[test]
+    fn test_mark_nonce_range() {
+        let domain = nonce_domain("orderCoffee");
+        let mut store = BTreeSet::new();
+
+        mark_nonce_range(&mut store, domain, U256::from(10), 3).unwrap();
+        for nonce in 10..13 {
+            assert!(is_nonce_used(&store, domain, U256::from(nonce)));
+        }
+        assert!(!is_nonce_used(&store, domain, U256::from(13)));
+        assert_eq!(are_nonces_unused(&store, domain, U256::from(13), 5), Ok(true));
+    }
+
+    #[test]
+    fn test_mark_nonce_range_partially_used() {
+        let domain = nonce_domain("orderCoffee");
+        let mut store = BTreeSet::new();
+        mark_nonce_used(&mut store, domain, U256::from(5));
+        mark_nonce_used(&mut store, domain, U256::from(7));
+        let before = store.clone();
+
+        assert_eq!(are_nonces_unused(&store, domain, U256::from(3), 5), Ok(false));
+
+        // The batch is rejected and nothing in the range is marked
+        assert_eq!(
+            mark_nonce_range(&mut store, domain, U256::from(3), 5),
+            Err(NonceError::AlreadyUsed)
+        );
+        assert_eq!(store, before);
+
+        // Used in another domain only
+        assert_eq!(
+            are_nonces_unused(&store, nonce_domain("refundCoffee"), U256::from(3), 5),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn test_raw_key_still_rejected() {
+        // Marked by a deployment that predates namespacing
+        let mut store = BTreeSet::new();
+        store.insert(U256::from(42));
+        let domain = nonce_domain("orderCoffee");
+
+        assert!(is_nonce_used(&store, domain, U256::from(42)));
+        assert_eq!(
+            mark_nonce_range(&mut store, domain, U256::from(40), 4),
+            Err(NonceError::AlreadyUsed)
+        );
+
+        // New nonces are marked under the namespaced key only
+        mark_nonce_used(&mut store, domain, U256::from(43));
+        assert!(store.contains(&namespaced_nonce(domain, U256::from(43))));
+        assert!(!store.contains(&U256::from(43)));
+    }
+
+    #[test]
+    fn test_consume_nonce_rejects_reentry() {
+        let domain = nonce_domain("orderCoffee");
+        let mut store = BTreeSet::new();
+
+        // `order_coffee` consumes the nonce before calling `pay`...
+        assert_eq!(consume_nonce(&mut store, domain, U256::from(1)), Ok(()));
+
+        // ...so `pay` re-entering `order_coffee` with the same nonce is rejected
+        assert_eq!(
+            consume_nonce(&mut store, domain, U256::from(1)),
+            Err(NonceError::AlreadyUsed)
+        );
+
+        assert_eq!(consume_nonce(&mut store, domain, U256::from(2)), Ok(()));
+    }
+}
@@ -0,0 +1,120 @@
+//! Order Checks
+//!
+//! Pure checks for plaintext order amounts in EVVM service contracts. The
+//! client signs a total price; the contract recomputes it from its own price
+//! list, so a client cannot pay less than the listed price by signing a
+//! smaller total.
+//!
+//! ```ignore
+//! use fhe_stylus::order::{check_order_total, OrderError};
+//!
+//! let unit_price = self.menu.get(keccak256(coffee_type.as_bytes()));
+//! check_order_total(unit_price, quantity, total_price).map_err(|err| match err {
+//!     OrderError::NotListed => b"Coffee not on menu".to_vec(),
+//!     OrderError::PriceMismatch => b"Price mismatch".to_vec(),
+//!     OrderError::PriceOverflow => b"Price overflow".to_vec(),
+//!     OrderError::QuantityTooLarge => b"Quantity too large".to_vec(),
+//! })?;
+//! ```
+
+use stylus_sdk::alloy_primitives::U256;
+
+/// Errors returned by the checks in this module
+#[derive(Debug, PartialEq, Eq)]
+pub enum OrderError {
+    /// The item has no listed price
+    NotListed,
+    /// The client-supplied total differs from `unit_price * quantity`
+    PriceMismatch,
+    /// `unit_price * quantity` overflows `U256`
+    PriceOverflow,
+    /// The quantity exceeds the per-order maximum
+    QuantityTooLarge,
+}
+
+/// Check an order quantity against a per-order maximum
+///
+/// # Parameters
+/// * `quantity` - Number of units ordered
+/// * `max_quantity` - Maximum units per order; zero means no limit
+///
+/// # Errors
+/// * `OrderError::QuantityTooLarge` - `quantity` exceeds a non-zero `max_quantity`
+pub fn check_quantity(quantity: U256, max_quantity: U256) -> Result<(), OrderError> {
+    if !max_quantity.is_zero() && quantity > max_quantity {
+        return Err(OrderError::QuantityTooLarge);
+    }
+    Ok(())
+}
+
+/// Check a client-supplied order total against the listed unit price
+///
+/// # Parameters
+/// * `unit_price` - Listed price per unit; zero means the item is not listed
+/// * `quantity` - Number of units ordered
+/// * `total_price` - Total price signed by the client
+///
+/// # Errors
+/// * `OrderError::NotListed` - `unit_price` is zero
+/// * `OrderError::PriceOverflow` - `unit_price * quantity` overflows
+/// * `OrderError::PriceMismatch` - `total_price` differs from `unit_price * quantity`
+pub fn check_order_total(unit_price: U256, quantity: U256, total_price: U256) -> Result<(), OrderError> {
+    if unit_price.is_zero() {
+        return Err(OrderError::NotListed);
+    }
+
+    let expected_total = unit_price
+        .checked_mul(quantity)
+        .ok_or(OrderError::PriceOverflow)?;
+
+    if expected_total != total_price {
+        return Err(OrderError::PriceMismatch);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_order_total() {
+        let price = U256::from(100);
+        assert!(check_order_total(price, U256::from(2), U256::from(200)).is_ok());
+
+        // Client-supplied total does not match the price list
+        assert_eq!(
+            check_order_total(price, U256::from(2), U256::from(1)),
+            Err(OrderError::PriceMismatch)
+        );
+
+        // Item not listed
+        assert_eq!(
+            check_order_total(U256::ZERO, U256::from(2), U256::ZERO),
+            Err(OrderError::NotListed)
+        );
+    }
+
+    #[test]
+    fn test_check_quantity() {
+        let max = U256::from(10);
+        assert!(check_quantity(max, max).is_ok());
+        assert_eq!(
+            check_quantity(max + U256::from(1), max),
+            Err(OrderError::QuantityTooLarge)
+        );
+
+        // Zero means no limit
+        assert!(check_quantity(U256::MAX, U256::ZERO).is_ok());
+    }
+
+    #[test]
+    fn test_check_order_total_overflow() {
+        assert_eq!(
+            check_order_total(U256::MAX, U256::from(2), U256::ZERO),
+            Err(OrderError::PriceOverflow)
+        );
+        assert!(check_order_total(U256::MAX, U256::from(1), U256::MAX).is_ok());
+    }
+}
@@ -0,0 +1,44 @@
+//! Revert-on-Panic Handler
+//!
+//! Enabled with the `panic-revert` feature. Replaces the usual
+//! `loop {}` panic handler with one that reverts the transaction and returns
+//! the panic message as revert data, so a failed call on testnet shows a
+//! readable reason instead of an opaque trap.
+//!
+//! ```toml
+//! [dependencies]
+//! fhe-stylus = { path = "../fhe-stylus", features = ["panic-revert"] }
+//! ```
+//!
+//! A contract enabling this feature must not define its own `#[panic_handler]`
+//! and must be built without `std`, which brings its own panic implementation.
+//! EVVMCafhe enforces both: its handler is compiled out, and enabling
+//! `panic-revert` together with its default `std` feature fails on WASM.
+//!
+//! # Code Size
+//! Only static messages (`panic!("literal")`, `expect("literal")`) are
+//! forwarded; formatted messages fall back to a generic `"panic"` reason.
+//! This avoids pulling `core::fmt` into the binary, which would cost several
+//! KB of the 24KB limit. The handler itself adds well under 1KB, but it keeps
+//! every panic message string in the WASM data section, so leave the feature
+//! off for size-critical release builds.
+
+use stylus_sdk::hostio;
+
+/// Revert reason used when the panic message is not a static string
+const GENERIC_PANIC_REASON: &[u8] = b"panic";
+
+#[panic_handler]
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    let reason = info
+        .message()
+        .as_str()
+        .map(str::as_bytes)
+        .unwrap_or(GENERIC_PANIC_REASON);
+
+    // Exiting with a non-zero status reverts and returns the result as revert data
+    unsafe {
+        hostio::write_result(reason.as_ptr(), reason.len());
+        hostio::exit_early(1)
+    }
+}
@@ -0,0 +1,167 @@
+//! Failed External Call Reporting
+//!
+//! `stylus_sdk::call::Error` only says that a call failed. Mapping it to a
+//! single static error (`.map_err(|_| b"Payment failed")`) throws away the
+//! callee's revert data, so an out-of-gas, a `require` in the callee and a
+//! malformed return value all look the same off-chain.
+//!
+//! [`call_failed`] keeps the cause and appends it to a static prefix:
+//!
+//! ```text
+//! <prefix>: revert <callee revert data>
+//! <prefix>: decode
+//! ```
+//!
+//! The prefix comes first, so tooling that matches on it keeps working:
+//!
+//! ```ignore
+//! use fhe_stylus::revert::call_failed;
+//!
+//! let evvm_id = evvm_core
+//!     .evvm_id(Call::new_in(self))
+//!     .map_err(call_failed(b"Payment failed"))?;
+//! ```
+//!
+//! # EVVMCore Payments
+//! EVVMCore rejects payments with `require(..., "<reason>")`.
+//! [`classify_pay_revert`] recognizes the reasons a caller can act on, so a
+//! contract can report them as specific errors instead of raw revert data.
+
+use alloc::vec::Vec;
+
+use stylus_sdk::alloy_sol_types::{Revert, SolError};
+use stylus_sdk::call::Error;
+
+/// Tag appended to the prefix when the callee reverted
+pub const TAG_REVERT: &[u8] = b": revert ";
+
+/// Tag appended to the prefix when the return data could not be decoded
+pub const TAG_DECODE: &[u8] = b": decode";
+
+/// Cause of a failed external call
+#[derive(Debug, PartialEq, Eq)]
+pub enum CallFailure {
+    /// The callee reverted with this data (empty usually means out of gas)
+    Revert(Vec<u8>),
+    /// The callee returned data that could not be ABI-decoded
+    Decode,
+}
+
+impl From<Error> for CallFailure {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Revert(data) => CallFailure::Revert(data),
+            Error::AbiDecodingFailed(_) => CallFailure::Decode,
+        }
+    }
+}
+
+impl CallFailure {
+    /// Encode as `<prefix>: revert <data>` or `<prefix>: decode`
+    pub fn encode(self, prefix: &[u8]) -> Vec<u8> {
+        let mut out = prefix.to_vec();
+        match self {
+            CallFailure::Revert(data) => {
+                out.extend_from_slice(TAG_REVERT);
+                out.extend_from_slice(&data);
+            }
+            CallFailure::Decode => out.extend_from_slice(TAG_DECODE),
+        }
+        out
+    }
+}
+
+/// Known reason for an `EVVMCore.pay` revert
+#[derive(Debug, PartialEq, Eq)]
+pub enum PayRejection {
+    /// The payment nonce was already used or is invalid
+    Nonce,
+    /// The payment signature is invalid or missing
+    Signature,
+    /// The token is not whitelisted in EVVMCore
+    TokenNotWhitelisted,
+}
+
+/// Map an `EVVMCore.pay` revert to a known rejection reason
+///
+/// EVVMCore reverts with ABI-encoded `Error(string)`. Returns `None` for
+/// unknown reasons and for any other revert data.
+pub fn classify_pay_revert(data: &[u8]) -> Option<PayRejection> {
+    let reason = Revert::abi_decode(data, true).ok()?.reason;
+    match reason.as_str() {
+        "Nonce already used" | "Invalid nonce" => Some(PayRejection::Nonce),
+        "Invalid signature" | "Signature required" => Some(PayRejection::Signature),
+        "Token not whitelisted" => Some(PayRejection::TokenNotWhitelisted),
+        _ => None,
+    }
+}
+
+/// `map_err` adapter that keeps the cause of an external call failure
+///
+/// # Parameters
+/// * `prefix` - Static error the contract reports for this call
+pub fn call_failed(prefix: &'static [u8]) -> impl FnOnce(Error) -> Vec<u8> {
+    move |err| CallFailure::from(err).encode(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_call_failure_encoding() {
+        let revert = CallFailure::Revert(b"Insufficient balance".to_vec()).encode(b"Payment failed");
+        assert_eq!(revert, b"Payment failed: revert Insufficient balance".to_vec());
+
+        assert_eq!(
+            CallFailure::Decode.encode(b"Payment failed"),
+            b"Payment failed: decode".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_call_failed_keeps_cause() {
+        assert_eq!(
+            CallFailure::from(Error::Revert(b"out of gas".to_vec())),
+            CallFailure::Revert(b"out of gas".to_vec())
+        );
+        assert_eq!(
+            call_failed(b"Staker check failed")(Error::Revert(b"out of gas".to_vec())),
+            b"Staker check failed: revert out of gas".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_call_failed_view_read() {
+        // A failed balance read is reported with its cause, never as a value
+        let revert = Revert { reason: "Not initialized".to_string() }.abi_encode();
+        let mut expected = b"Balance read failed: revert ".to_vec();
+        expected.extend_from_slice(&revert);
+        assert_eq!(call_failed(b"Balance read failed")(Error::Revert(revert)), expected);
+
+        assert_eq!(
+            call_failed(b"Balance read failed")(Error::AbiDecodingFailed(
+                stylus_sdk::alloy_sol_types::Error::Overrun
+            )),
+            b"Balance read failed: decode".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_classify_pay_revert() {
+        let revert = |reason: &str| Revert { reason: reason.to_string() }.abi_encode();
+
+        assert_eq!(classify_pay_revert(&revert("Nonce already used")), Some(PayRejection::Nonce));
+        assert_eq!(classify_pay_revert(&revert("Invalid signature")), Some(PayRejection::Signature));
+        assert_eq!(
+            classify_pay_revert(&revert("Token not whitelisted")),
+            Some(PayRejection::TokenNotWhitelisted)
+        );
+
+        // Unknown reasons and non-`Error(string)` data are not classified
+        assert_eq!(classify_pay_revert(&revert("Not initialized")), None);
+        assert_eq!(classify_pay_revert(&[0xde, 0xad, 0xbe, 0xef]), None);
+        assert_eq!(classify_pay_revert(&[]), None);
+    }
+}
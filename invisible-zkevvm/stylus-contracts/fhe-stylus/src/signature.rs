@@ -3,6 +3,8 @@
 //! Official EVVM library for verifying EIP-191 signatures in Stylus contracts.
 //! Follows EVVM specification: "<evvmID>,<functionName>,<inputs>"
 //!
+//! EIP-712 typed data is also supported through [`SignatureRecover::verify_typed_data`].
+//!
 //! # Example
 //! ```ignore
 //! use fhe_stylus::signature::SignatureRecover;
@@ -20,23 +22,44 @@
 //! }
 //! ```
 
-use stylus_sdk::alloy_primitives::{Address, keccak256, B256};
-use alloc::string::ToString;
+use stylus_sdk::alloy_primitives::{b256, Address, keccak256, B256, U256};
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::format;
+use core::fmt::Display;
+
+use crate::convert::u256_to_b256;
 
 /// Signature recovery utilities for EVVM
 pub struct SignatureRecover;
 
+/// Half of the secp256k1 curve order (`n / 2`)
+///
+/// Signatures with `s` above this value are malleable (EIP-2) and are rejected
+/// by [`SignatureRecover::split_signature_canonical`].
+pub const SECP256K1N_HALF: B256 =
+    b256!("7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0");
+
+/// EIP-191 personal-sign prefix used by [`SignatureRecover::recover_signer`]
+pub const ETH_SIGNED_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+/// EIP-712 domain type string used by [`SignatureRecover::domain_separator`]
+pub const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
 /// Errors that can occur during signature verification
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum SignatureError {
-    /// Signature has invalid length (must be 65 bytes)
+    /// Signature has invalid length (must be 65 bytes, or 64 bytes compact)
     InvalidLength,
     /// Signature v value is invalid (must be 27 or 28)
     InvalidV,
     /// Recovery failed
     RecoveryFailed,
+    /// Signature s value is in the upper half of the curve order (malleable)
+    HighS,
+    /// Signed deadline has passed
+    Expired,
 }
 
 impl SignatureRecover {
@@ -86,16 +109,160 @@ impl SignatureRecover {
         signature: &[u8],
         expected_signer: Address,
     ) -> Result<bool, SignatureError> {
-        // Concatenate message components: "<evvmID>,<functionName>,<inputs>"
-        let message = format!("{},{},{}", evvm_id, function_name, inputs);
-
         // Recover the signer from the signature
-        let recovered_signer = Self::recover_signer(&message, signature)?;
+        let recovered_signer =
+            Self::recover_evvm_signer(evvm_id, function_name, inputs, signature)?;
 
         // Compare with expected signer
         Ok(recovered_signer == expected_signer)
     }
 
+    /// Verifies a chain-bound signature for EVVM function calls
+    ///
+    /// Same as [`Self::signature_verification`], but the signed message also
+    /// commits to the chain id so a signature cannot be replayed on another
+    /// chain that shares the same `evvmID`.
+    ///
+    /// # Message Format
+    /// The chain id is prepended to the standard EVVM message:
+    /// ```text
+    /// "<chainId>,<evvmID>,<functionName>,<inputs>"
+    /// ```
+    ///
+    /// For example, on Arbitrum Sepolia:
+    /// ```text
+    /// "421614,1234,orderCoffee,Espresso,2,100,42"
+    /// ```
+    pub fn signature_verification_with_chain(
+        chain_id: u64,
+        evvm_id: &str,
+        function_name: &str,
+        inputs: &str,
+        signature: &[u8],
+        expected_signer: Address,
+    ) -> Result<bool, SignatureError> {
+        let message = Self::evvm_message_with_chain(chain_id, evvm_id, function_name, inputs);
+
+        let recovered_signer = Self::recover_signer(&message, signature)?;
+
+        Ok(recovered_signer == expected_signer)
+    }
+
+    /// Verifies a signature that expires at a signed deadline
+    ///
+    /// The deadline is appended as the last input field, so the client signs
+    /// `"<evvmID>,<functionName>,<inputs>,<deadline>"` (see
+    /// [`Self::inputs_with_deadline`]) and cannot have it changed later.
+    ///
+    /// # Parameters
+    /// * `deadline` - Last timestamp (inclusive) at which the signature is valid
+    /// * `now` - Current time; pass `U256::from(block::timestamp())`
+    ///
+    /// # Errors
+    /// * `SignatureError::Expired` - `now > deadline`, checked before recovery
+    pub fn verify_with_deadline(
+        evvm_id: &str,
+        function_name: &str,
+        inputs: &str,
+        deadline: U256,
+        now: U256,
+        signature: &[u8],
+        expected_signer: Address,
+    ) -> Result<bool, SignatureError> {
+        if now > deadline {
+            return Err(SignatureError::Expired);
+        }
+
+        Self::signature_verification(
+            evvm_id,
+            function_name,
+            &Self::inputs_with_deadline(inputs, deadline),
+            signature,
+            expected_signer,
+        )
+    }
+
+    /// Appends a deadline to the input fields: `"<inputs>,<deadline>"`
+    pub fn inputs_with_deadline(inputs: &str, deadline: U256) -> String {
+        format!("{},{}", inputs, deadline)
+    }
+
+    /// Recovers the signer of an EVVM function call message
+    ///
+    /// Builds the `"<evvmID>,<functionName>,<inputs>"` message and returns the
+    /// address that signed it, without needing to know the signer in advance.
+    /// Useful for relayers that look up the signer after recovery.
+    ///
+    /// # Parameters
+    /// * `evvm_id` - The EVVM ID string (e.g., "1234")
+    /// * `function_name` - The name of the function being called (e.g., "orderCoffee")
+    /// * `inputs` - The concatenated input parameters, comma-separated
+    /// * `signature` - The signature bytes (65 bytes: r=32, s=32, v=1)
+    ///
+    /// # Returns
+    /// * `Result<Address, SignatureError>` - The recovered signer address
+    pub fn recover_evvm_signer(
+        evvm_id: &str,
+        function_name: &str,
+        inputs: &str,
+        signature: &[u8],
+    ) -> Result<Address, SignatureError> {
+        let message = Self::evvm_message(evvm_id, function_name, inputs);
+        Self::recover_signer(&message, signature)
+    }
+
+    /// Joins input fields with commas, as expected in the EVVM message
+    ///
+    /// Empty fields are kept, so `["a", "", "b"]` becomes `"a,,b"`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let inputs = SignatureRecover::build_inputs(&[
+    ///     &coffee_type,
+    ///     &quantity.to_string(),
+    ///     &total_price.to_string(),
+    ///     &nonce.to_string(),
+    /// ]);
+    /// ```
+    pub fn build_inputs(fields: &[&str]) -> String {
+        fields.join(",")
+    }
+
+    /// Computes the digest an off-chain signer must sign for an EVVM message
+    ///
+    /// This is exactly what [`Self::signature_verification`] recovers against:
+    ///
+    /// ```text
+    /// keccak256("\x19Ethereum Signed Message:\n" + len(message) + message)
+    /// message = "<evvmID>,<functionName>,<inputs>"
+    /// ```
+    ///
+    /// Test harnesses can compare it with ethers' `hashMessage(message)`.
+    pub fn message_hash(evvm_id: &str, function_name: &str, inputs: &str) -> B256 {
+        Self::eth_signed_message_hash(&Self::evvm_message(evvm_id, function_name, inputs))
+    }
+
+    /// Builds the EVVM message that clients sign
+    ///
+    /// # Returns
+    /// * `String` - `"<evvmID>,<functionName>,<inputs>"`
+    pub fn evvm_message(evvm_id: &str, function_name: &str, inputs: &str) -> String {
+        format!("{},{},{}", evvm_id, function_name, inputs)
+    }
+
+    /// Builds the chain-bound EVVM message that clients sign
+    ///
+    /// # Returns
+    /// * `String` - `"<chainId>,<evvmID>,<functionName>,<inputs>"`
+    pub fn evvm_message_with_chain(
+        chain_id: u64,
+        evvm_id: &str,
+        function_name: &str,
+        inputs: &str,
+    ) -> String {
+        format!("{},{}", chain_id, Self::evvm_message(evvm_id, function_name, inputs))
+    }
+
     /// Recovers the signer address from a message and signature
     ///
     /// # Parameters
@@ -114,30 +281,154 @@ impl SignatureRecover {
         message: &str,
         signature: &[u8],
     ) -> Result<Address, SignatureError> {
-        // Create EIP-191 prefixed message hash
-        let message_bytes = message.as_bytes();
-        let message_len = message_bytes.len().to_string();
+        Self::recover_signer_with_prefix(ETH_SIGNED_MESSAGE_PREFIX, message, signature)
+    }
 
-        // Build: "\x19Ethereum Signed Message:\n" + len + message
-        let mut eth_message = Vec::new();
-        eth_message.extend_from_slice(b"\x19Ethereum Signed Message:\n");
-        eth_message.extend_from_slice(message_len.as_bytes());
-        eth_message.extend_from_slice(message_bytes);
+    /// Recovers the signer address using a custom personal-sign prefix
+    ///
+    /// Same as [`Self::recover_signer`], but `prefix` replaces
+    /// `"\x19Ethereum Signed Message:\n"` for wallets or L2 deployments that
+    /// sign with a different prefix. The decimal message length is still
+    /// appended after the prefix.
+    ///
+    /// # Parameters
+    /// * `prefix` - Prefix bytes placed before the message length
+    /// * `message` - The message that was signed (plain text)
+    /// * `signature` - The signature bytes (65 bytes)
+    pub fn recover_signer_with_prefix(
+        prefix: &[u8],
+        message: &str,
+        signature: &[u8],
+    ) -> Result<Address, SignatureError> {
+        let message_hash = Self::prefixed_message_hash(prefix, message);
 
-        // Hash the prefixed message
-        let message_hash = keccak256(&eth_message);
+        Self::recover_from_hash(&message_hash, signature)
+    }
 
+    /// Recovers the signer address from a precomputed 32-byte digest
+    ///
+    /// Unlike [`Self::recover_signer`], no EIP-191 prefix is applied: the hash
+    /// is passed to ecrecover as-is. Use this for digests computed elsewhere,
+    /// e.g. EIP-712 (see [`Self::typed_data_hash`]).
+    ///
+    /// # Parameters
+    /// * `hash` - The digest that was signed
+    /// * `signature` - The signature bytes (65 bytes)
+    ///
+    /// # Returns
+    /// * `Result<Address, SignatureError>` - The recovered signer address
+    pub fn recover_from_hash(
+        hash: &B256,
+        signature: &[u8],
+    ) -> Result<Address, SignatureError> {
         // Split signature into r, s, v components
         let (r, s, v) = Self::split_signature(signature)?;
 
         // Recover the address using ecrecover
-        Self::ecrecover(&message_hash, v, &r, &s)
+        Self::ecrecover(hash, v, &r, &s)
+    }
+
+    /// Computes the EIP-191 personal-sign hash of a message
+    ///
+    /// ```text
+    /// keccak256("\x19Ethereum Signed Message:\n" + len(message) + message)
+    /// ```
+    pub fn eth_signed_message_hash(message: &str) -> B256 {
+        Self::prefixed_message_hash(ETH_SIGNED_MESSAGE_PREFIX, message)
+    }
+
+    /// Computes `keccak256(prefix + len(message) + message)`
+    ///
+    /// With [`ETH_SIGNED_MESSAGE_PREFIX`] this is the EIP-191 personal-sign hash.
+    pub fn prefixed_message_hash(prefix: &[u8], message: &str) -> B256 {
+        let message_bytes = message.as_bytes();
+        let message_len = message_bytes.len().to_string();
+
+        // Build: prefix + len + message
+        let mut prefixed_message = Vec::new();
+        prefixed_message.extend_from_slice(prefix);
+        prefixed_message.extend_from_slice(message_len.as_bytes());
+        prefixed_message.extend_from_slice(message_bytes);
+
+        // Hash the prefixed message
+        keccak256(&prefixed_message)
+    }
+
+    /// Verifies an EIP-712 typed-data signature
+    ///
+    /// # Parameters
+    /// * `domain_separator` - The EIP-712 domain separator (see [`Self::domain_separator`])
+    /// * `struct_hash` - `hashStruct(message)` of the signed typed data
+    /// * `signature` - The signature bytes (65 bytes: r=32, s=32, v=1)
+    /// * `expected_signer` - The address that should have signed the data
+    ///
+    /// # Returns
+    /// * `Result<bool, SignatureError>` - True if the recovered signer matches
+    ///
+    /// # Digest Format
+    /// ```text
+    /// keccak256(0x19 0x01 || domainSeparator || structHash)
+    /// ```
+    pub fn verify_typed_data(
+        domain_separator: B256,
+        struct_hash: B256,
+        signature: &[u8],
+        expected_signer: Address,
+    ) -> Result<bool, SignatureError> {
+        let digest = Self::typed_data_hash(&domain_separator, &struct_hash);
+        let recovered_signer = Self::recover_from_hash(&digest, signature)?;
+
+        Ok(recovered_signer == expected_signer)
+    }
+
+    /// Computes the EIP-712 digest for a domain separator and struct hash
+    pub fn typed_data_hash(domain_separator: &B256, struct_hash: &B256) -> B256 {
+        let mut data = [0u8; 66];
+        data[0] = 0x19;
+        data[1] = 0x01;
+        data[2..34].copy_from_slice(domain_separator.as_slice());
+        data[34..66].copy_from_slice(struct_hash.as_slice());
+
+        keccak256(data)
+    }
+
+    /// Computes an EIP-712 domain separator
+    ///
+    /// Uses the domain type [`EIP712_DOMAIN_TYPE`]:
+    /// ```text
+    /// EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)
+    /// ```
+    ///
+    /// # Example
+    /// ```ignore
+    /// let domain = SignatureRecover::domain_separator(
+    ///     "EVVMCafhe",
+    ///     "1",
+    ///     U256::from(421614),
+    ///     contract::address(),
+    /// );
+    /// ```
+    pub fn domain_separator(
+        name: &str,
+        version: &str,
+        chain_id: U256,
+        verifying_contract: Address,
+    ) -> B256 {
+        // abi.encode(typeHash, keccak256(name), keccak256(version), chainId, verifyingContract)
+        let mut data = [0u8; 160];
+        data[0..32].copy_from_slice(keccak256(EIP712_DOMAIN_TYPE.as_bytes()).as_slice());
+        data[32..64].copy_from_slice(keccak256(name.as_bytes()).as_slice());
+        data[64..96].copy_from_slice(keccak256(version.as_bytes()).as_slice());
+        data[96..128].copy_from_slice(u256_to_b256(chain_id).as_slice());
+        data[140..160].copy_from_slice(verifying_contract.as_slice());
+
+        keccak256(data)
     }
 
     /// Splits a signature into its r, s, and v components
     ///
     /// # Parameters
-    /// * `signature` - The signature bytes (must be exactly 65 bytes)
+    /// * `signature` - The signature bytes (65 bytes, or 64 bytes in EIP-2098 compact form)
     ///
     /// # Returns
     /// * `Result<(B256, B256, u8), SignatureError>` - The (r, s, v) components
@@ -146,7 +437,16 @@ impl SignatureRecover {
     /// ```text
     /// [  r: 32 bytes  ][  s: 32 bytes  ][ v: 1 byte ]
     /// ```
+    ///
+    /// # Compact Layout (EIP-2098)
+    /// ```text
+    /// [  r: 32 bytes  ][ yParity: 1 bit | s: 255 bits ]
+    /// ```
     pub fn split_signature(signature: &[u8]) -> Result<(B256, B256, u8), SignatureError> {
+        if signature.len() == 64 {
+            return Ok(Self::split_compact_signature(signature));
+        }
+
         if signature.len() != 65 {
             return Err(SignatureError::InvalidLength);
         }
@@ -177,6 +477,94 @@ impl SignatureRecover {
         Ok((r, s, v))
     }
 
+    /// Splits a signature whose `v` may carry an EIP-155 chain id
+    ///
+    /// Raw-transaction signatures encode `v = 35 + 2 * chainId + yParity`,
+    /// which does not fit in one byte for most chains, so `v` is read as the
+    /// 1 to 8 big-endian bytes after `r || s` (65 to 72 bytes in total).
+    /// Plain `{27, 28}` and `{0, 1}` values are accepted as in
+    /// [`Self::split_signature`].
+    ///
+    /// Wallet `personal_sign` output always uses 27/28; this is only needed for
+    /// signatures produced in raw-transaction style.
+    ///
+    /// # Returns
+    /// `(r, s, v)` with `v = 27 + yParity`, ready for ecrecover.
+    ///
+    /// # Errors
+    /// * `SignatureError::InvalidLength` - Not 65 to 72 bytes
+    /// * `SignatureError::InvalidV` - `v` encodes a different chain id, or is not a valid recovery id
+    pub fn split_signature_eip155(
+        signature: &[u8],
+        chain_id: u64,
+    ) -> Result<(B256, B256, u8), SignatureError> {
+        if signature.len() < 65 || signature.len() > 72 {
+            return Err(SignatureError::InvalidLength);
+        }
+
+        let r = B256::from_slice(&signature[0..32]);
+        let s = B256::from_slice(&signature[32..64]);
+
+        // Big-endian v
+        let v = signature[64..]
+            .iter()
+            .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte));
+
+        let y_parity = match v {
+            0 | 1 => v,
+            27 | 28 => v - 27,
+            _ => {
+                let offset = chain_id
+                    .checked_mul(2)
+                    .and_then(|x| x.checked_add(35))
+                    .ok_or(SignatureError::InvalidV)?;
+                match v.checked_sub(offset) {
+                    Some(parity @ (0 | 1)) => parity,
+                    _ => return Err(SignatureError::InvalidV),
+                }
+            }
+        };
+
+        Ok((r, s, 27 + y_parity as u8))
+    }
+
+    /// Splits an EIP-2098 compact signature (exactly 64 bytes)
+    fn split_compact_signature(signature: &[u8]) -> (B256, B256, u8) {
+        // Extract r (first 32 bytes)
+        let mut r_bytes = [0u8; 32];
+        r_bytes.copy_from_slice(&signature[0..32]);
+        let r = B256::from(r_bytes);
+
+        // yParity is the top bit of vs; s is vs with that bit cleared
+        let mut s_bytes = [0u8; 32];
+        s_bytes.copy_from_slice(&signature[32..64]);
+        let y_parity = s_bytes[0] >> 7;
+        s_bytes[0] &= 0x7f;
+        let s = B256::from(s_bytes);
+
+        (r, s, 27 + y_parity)
+    }
+
+    /// Splits a signature, rejecting malleable high-s values (EIP-2)
+    ///
+    /// Same as [`Self::split_signature`], but also requires `s <= secp256k1n / 2`,
+    /// matching OpenZeppelin's ECDSA library. Use this when the signature itself
+    /// acts as replay protection, since a high-s signature can be replayed with
+    /// `s' = n - s` and a flipped `v`.
+    ///
+    /// # Returns
+    /// * `Result<(B256, B256, u8), SignatureError>` - The (r, s, v) components,
+    ///   or `SignatureError::HighS` if `s` is not canonical
+    pub fn split_signature_canonical(signature: &[u8]) -> Result<(B256, B256, u8), SignatureError> {
+        let (r, s, v) = Self::split_signature(signature)?;
+
+        if s > SECP256K1N_HALF {
+            return Err(SignatureError::HighS);
+        }
+
+        Ok((r, s, v))
+    }
+
     /// Performs ecrecover to get the signer address
     ///
     /// # Parameters
@@ -190,27 +578,14 @@ impl SignatureRecover {
     ///
     /// # Note
     /// This uses the EVM's ecrecover precompile (address 0x01)
+    #[cfg(not(test))]
     fn ecrecover(
         message_hash: &B256,
         v: u8,
         r: &B256,
         s: &B256,
     ) -> Result<Address, SignatureError> {
-        // Build the input for ecrecover precompile:
-        // [hash: 32 bytes][v: 32 bytes][r: 32 bytes][s: 32 bytes]
-        let mut input = [0u8; 128];
-
-        // Copy message hash
-        input[0..32].copy_from_slice(message_hash.as_slice());
-
-        // Copy v (as 32 bytes, right-aligned)
-        input[63] = v;
-
-        // Copy r
-        input[64..96].copy_from_slice(r.as_slice());
-
-        // Copy s
-        input[96..128].copy_from_slice(s.as_slice());
+        let input = Self::ecrecover_input(message_hash, v, r, s);
 
         // Call ecrecover precompile at address 0x01
         use stylus_sdk::call::RawCall;
@@ -236,11 +611,106 @@ impl SignatureRecover {
             Err(_) => Err(SignatureError::RecoveryFailed),
         }
     }
+
+    /// Native stand-in for the ecrecover precompile in unit tests
+    ///
+    /// The precompile is only reachable on-chain; this recovers with k256 so
+    /// the hashing, splitting and `v` handling above it run against real
+    /// signatures.
+    #[cfg(test)]
+    fn ecrecover(
+        message_hash: &B256,
+        v: u8,
+        r: &B256,
+        s: &B256,
+    ) -> Result<Address, SignatureError> {
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(r.as_slice());
+        bytes[32..64].copy_from_slice(s.as_slice());
+        bytes[64] = v;
+
+        alloy_primitives::Signature::try_from(&bytes[..])
+            .and_then(|signature| signature.recover_address_from_prehash(message_hash))
+            .map_err(|_| SignatureError::RecoveryFailed)
+    }
+
+    /// Builds the ecrecover precompile input
+    ///
+    /// ```text
+    /// [hash: 32 bytes][v: 32 bytes][r: 32 bytes][s: 32 bytes]
+    /// ```
+    fn ecrecover_input(message_hash: &B256, v: u8, r: &B256, s: &B256) -> [u8; 128] {
+        let mut input = [0u8; 128];
+
+        // Copy message hash
+        input[0..32].copy_from_slice(message_hash.as_slice());
+
+        // Copy v (as 32 bytes, right-aligned)
+        input[63] = v;
+
+        // Copy r
+        input[64..96].copy_from_slice(r.as_slice());
+
+        // Copy s
+        input[96..128].copy_from_slice(s.as_slice());
+
+        input
+    }
+}
+
+/// Builder for EVVM signed messages
+///
+/// Enforces the `"<evvmID>,<functionName>,<inputs>"` structure so the field
+/// order and separators match the off-chain signer.
+///
+/// # Example
+/// ```ignore
+/// let message = EvvmMessage::new(&evvm_id.to_string(), "orderCoffee")
+///     .field(&coffee_type)
+///     .field(quantity)
+///     .field(total_price)
+///     .field(nonce)
+///     .build();
+/// // "1234,orderCoffee,Espresso,2,100,42"
+/// ```
+#[derive(Debug, Clone)]
+pub struct EvvmMessage {
+    evvm_id: String,
+    function_name: String,
+    fields: Vec<String>,
+}
+
+impl EvvmMessage {
+    /// Start a message for an EVVM function call
+    pub fn new(evvm_id: &str, function_name: &str) -> Self {
+        Self {
+            evvm_id: evvm_id.to_string(),
+            function_name: function_name.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Append an input field
+    pub fn field(mut self, value: impl Display) -> Self {
+        self.fields.push(value.to_string());
+        self
+    }
+
+    /// Get the comma-separated inputs part of the message
+    pub fn inputs(&self) -> String {
+        self.fields.join(",")
+    }
+
+    /// Build the full message string
+    pub fn build(&self) -> String {
+        SignatureRecover::evvm_message(&self.evvm_id, &self.function_name, &self.inputs())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use stylus_sdk::alloy_primitives::{address, b256};
 
     #[test]
     fn test_split_signature_valid() {
@@ -257,7 +727,7 @@ mod tests {
 
     #[test]
     fn test_split_signature_invalid_length() {
-        let sig = [0u8; 64]; // Wrong length
+        let sig = [0u8; 63]; // Wrong length
         let result = SignatureRecover::split_signature(&sig);
         assert!(matches!(result, Err(SignatureError::InvalidLength)));
     }
@@ -274,6 +744,43 @@ mod tests {
         assert_eq!(v, 27);
     }
 
+    #[test]
+    fn test_split_signature_eip155() {
+        // Mainnet: v = 35 + 2 * 1 + yParity
+        let mut sig = [0u8; 65];
+        sig[64] = 37;
+        assert_eq!(SignatureRecover::split_signature_eip155(&sig, 1).unwrap().2, 27);
+        sig[64] = 38;
+        assert_eq!(SignatureRecover::split_signature_eip155(&sig, 1).unwrap().2, 28);
+
+        // Signed for a different chain
+        assert!(matches!(
+            SignatureRecover::split_signature_eip155(&sig, 5),
+            Err(SignatureError::InvalidV)
+        ));
+
+        // Legacy v still works
+        sig[64] = 27;
+        assert_eq!(SignatureRecover::split_signature_eip155(&sig, 1).unwrap().2, 27);
+    }
+
+    #[test]
+    fn test_split_signature_eip155_multibyte_v() {
+        // Arbitrum One: v = 35 + 2 * 42161 + 1 = 84358 = 0x014986
+        let mut sig = [0u8; 67];
+        sig[64..].copy_from_slice(&[0x01, 0x49, 0x86]);
+        assert_eq!(SignatureRecover::split_signature_eip155(&sig, 42161).unwrap().2, 28);
+
+        assert!(matches!(
+            SignatureRecover::split_signature_eip155(&[0u8; 64], 1),
+            Err(SignatureError::InvalidLength)
+        ));
+        assert!(matches!(
+            SignatureRecover::split_signature_eip155(&[0u8; 73], 1),
+            Err(SignatureError::InvalidLength)
+        ));
+    }
+
     #[test]
     fn test_split_signature_invalid_v() {
         let mut sig = [0u8; 65];
@@ -282,4 +789,345 @@ mod tests {
         let result = SignatureRecover::split_signature(&sig);
         assert!(matches!(result, Err(SignatureError::InvalidV)));
     }
+
+    #[test]
+    fn test_domain_separator_eip712_vector() {
+        // "Ether Mail" example from the EIP-712 specification
+        let domain = SignatureRecover::domain_separator(
+            "Ether Mail",
+            "1",
+            U256::from(1),
+            address!("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"),
+        );
+        assert_eq!(
+            domain,
+            b256!("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+        );
+    }
+
+    #[test]
+    fn test_typed_data_hash_eip712_vector() {
+        let domain = b256!("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f");
+        let mail = b256!("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e");
+
+        assert_eq!(
+            SignatureRecover::typed_data_hash(&domain, &mail),
+            b256!("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+    }
+
+    #[test]
+    fn test_verify_typed_data_invalid_length() {
+        let result = SignatureRecover::verify_typed_data(
+            B256::ZERO,
+            B256::ZERO,
+            &[0u8; 10],
+            Address::ZERO,
+        );
+        assert!(matches!(result, Err(SignatureError::InvalidLength)));
+    }
+
+    #[test]
+    fn test_split_signature_canonical_low_s() {
+        let mut sig = [0u8; 65];
+        sig[32..64].copy_from_slice(SECP256K1N_HALF.as_slice());
+        sig[64] = 28;
+
+        let result = SignatureRecover::split_signature_canonical(&sig);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_split_signature_canonical_high_s() {
+        let mut sig = [0u8; 65];
+        sig[32..64].copy_from_slice(SECP256K1N_HALF.as_slice());
+        sig[63] += 1; // n/2 + 1
+        sig[64] = 27;
+
+        let result = SignatureRecover::split_signature_canonical(&sig);
+        assert!(matches!(result, Err(SignatureError::HighS)));
+
+        // The non-canonical variant still accepts it
+        assert!(SignatureRecover::split_signature(&sig).is_ok());
+    }
+
+    #[test]
+    fn test_evvm_message_format() {
+        let message = SignatureRecover::evvm_message("1234", "orderCoffee", "Espresso,2,100,42");
+        assert_eq!(message, "1234,orderCoffee,Espresso,2,100,42");
+    }
+
+    #[test]
+    fn test_recover_evvm_signer_invalid_length() {
+        let result = SignatureRecover::recover_evvm_signer("1234", "orderCoffee", "", &[0u8; 3]);
+        assert!(matches!(result, Err(SignatureError::InvalidLength)));
+    }
+
+    #[test]
+    fn test_split_signature_compact_matches_full() {
+        let mut r = [0u8; 32];
+        r[31] = 0x11;
+        let mut s = [0u8; 32];
+        s[0] = 0x12;
+        s[31] = 0x34;
+
+        for y_parity in 0u8..2 {
+            let mut full = [0u8; 65];
+            full[0..32].copy_from_slice(&r);
+            full[32..64].copy_from_slice(&s);
+            full[64] = 27 + y_parity;
+
+            let mut compact = [0u8; 64];
+            compact[0..32].copy_from_slice(&r);
+            compact[32..64].copy_from_slice(&s);
+            compact[32] |= y_parity << 7;
+
+            // Same (r, s, v) means ecrecover yields the same address
+            assert_eq!(
+                SignatureRecover::split_signature(&compact).unwrap(),
+                SignatureRecover::split_signature(&full).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_evvm_message_with_chain() {
+        let arbitrum = SignatureRecover::evvm_message_with_chain(421614, "1234", "orderCoffee", "Espresso,2");
+        let sepolia = SignatureRecover::evvm_message_with_chain(11155111, "1234", "orderCoffee", "Espresso,2");
+
+        assert_eq!(arbitrum, "421614,1234,orderCoffee,Espresso,2");
+        // A signature over the Arbitrum message cannot verify for Sepolia
+        assert_ne!(arbitrum, sepolia);
+    }
+
+    #[test]
+    fn test_build_inputs() {
+        assert_eq!(
+            SignatureRecover::build_inputs(&["Espresso", "2", "100", "42"]),
+            "Espresso,2,100,42"
+        );
+        assert_eq!(SignatureRecover::build_inputs(&[]), "");
+        assert_eq!(SignatureRecover::build_inputs(&["a", "", "b"]), "a,,b");
+    }
+
+    #[test]
+    fn test_evvm_message_builder() {
+        let message = EvvmMessage::new("1234", "orderCoffee")
+            .field("Espresso")
+            .field(U256::from(2))
+            .field(100u64)
+            .field(42)
+            .build();
+        assert_eq!(message, "1234,orderCoffee,Espresso,2,100,42");
+
+        let empty = EvvmMessage::new("1234", "ping").build();
+        assert_eq!(empty, SignatureRecover::evvm_message("1234", "ping", ""));
+    }
+
+    #[test]
+    fn test_eth_signed_message_hash() {
+        assert_eq!(
+            SignatureRecover::eth_signed_message_hash("hello"),
+            b256!("50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750")
+        );
+
+        // The raw path hashes nothing extra, so the digests differ
+        assert_ne!(SignatureRecover::eth_signed_message_hash("hello"), keccak256("hello"));
+    }
+
+    #[test]
+    fn test_inputs_with_deadline() {
+        assert_eq!(
+            SignatureRecover::inputs_with_deadline("Espresso,2,100,42", U256::from(1_700_000_000u64)),
+            "Espresso,2,100,42,1700000000"
+        );
+    }
+
+    #[test]
+    fn test_verify_with_deadline_expired() {
+        let result = SignatureRecover::verify_with_deadline(
+            "1",
+            "orderCoffee",
+            "Espresso,2,100,42",
+            U256::from(100),
+            U256::from(101),
+            &[0u8; 65],
+            Address::ZERO,
+        );
+        assert!(matches!(result, Err(SignatureError::Expired)));
+    }
+
+    #[test]
+    fn test_verify_with_deadline_not_expired() {
+        // At the deadline the check passes and recovery runs (and rejects the
+        // malformed signature)
+        let result = SignatureRecover::verify_with_deadline(
+            "1",
+            "orderCoffee",
+            "Espresso,2,100,42",
+            U256::from(100),
+            U256::from(100),
+            &[0u8; 10],
+            Address::ZERO,
+        );
+        assert!(matches!(result, Err(SignatureError::InvalidLength)));
+    }
+
+    #[test]
+    fn test_message_hash() {
+        let inputs = SignatureRecover::build_inputs(&["Latte", "2", "200", "7"]);
+
+        // hashMessage("1,orderCoffee,Latte,2,200,7")
+        assert_eq!(
+            SignatureRecover::message_hash("1", "orderCoffee", &inputs),
+            b256!("08de5180611176fd0346eef521f9d3424c3588db53a69eec4e6031d4b3adc4bb")
+        );
+    }
+
+    #[test]
+    fn test_prefixed_message_hash() {
+        // Default prefix matches the EIP-191 hash
+        assert_eq!(
+            SignatureRecover::prefixed_message_hash(ETH_SIGNED_MESSAGE_PREFIX, "hello"),
+            SignatureRecover::eth_signed_message_hash("hello")
+        );
+
+        // A custom prefix signs a different digest, so the same signature
+        // recovers to a different address
+        assert_ne!(
+            SignatureRecover::prefixed_message_hash(b"\x19Custom Signed Message:\n", "hello"),
+            SignatureRecover::eth_signed_message_hash("hello")
+        );
+    }
+
+    // Vectors signed with private key 0x01, whose address is below
+    const VECTOR_SIGNER: Address = address!("7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+    /// Decode a 65-byte `r || s || v` signature
+    fn vector_signature(hex: &str) -> [u8; 65] {
+        let mut signature = [0u8; 65];
+        stylus_sdk::alloy_primitives::hex::decode_to_slice(hex, &mut signature).unwrap();
+        signature
+    }
+
+    #[test]
+    fn test_recover_evvm_signer_vector() {
+        // personal_sign("1,orderCoffee,Latte,2,200,7"), see test_message_hash
+        let signature = vector_signature(
+            "d47644539acec3da5e3ecf5fe8863c628a9c97e8b71e9ea9167a6f4f83c03c32\
+             4d07ef49e1686a9b2a46ac0a44db081bef1597af0e411752472669022a652a4d1c",
+        );
+        let inputs = SignatureRecover::build_inputs(&["Latte", "2", "200", "7"]);
+
+        assert_eq!(
+            SignatureRecover::recover_evvm_signer("1", "orderCoffee", &inputs, &signature),
+            Ok(VECTOR_SIGNER)
+        );
+        assert_eq!(
+            SignatureRecover::signature_verification(
+                "1",
+                "orderCoffee",
+                &inputs,
+                &signature,
+                VECTOR_SIGNER,
+            ),
+            Ok(true)
+        );
+
+        // Any change to the message recovers someone else
+        assert_ne!(
+            SignatureRecover::recover_evvm_signer("1", "orderCoffee", "Latte,3,200,7", &signature),
+            Ok(VECTOR_SIGNER)
+        );
+    }
+
+    #[test]
+    fn test_recover_from_hash_vector() {
+        // Raw signature over keccak256("fhe-stylus"), no EIP-191 prefix
+        let hash = keccak256("fhe-stylus");
+        let signature = vector_signature(
+            "082814f942fcda849553f3389d06d75720aa6fe908f0c38bd61d0cb264c4bfc2\
+             7954749c4814ee5b76c085d9543af8fc5bb6f8e195afbbd3ac128e52caadaa861b",
+        );
+
+        assert_eq!(
+            SignatureRecover::recover_from_hash(&hash, &signature),
+            Ok(VECTOR_SIGNER)
+        );
+    }
+
+    #[test]
+    fn test_recover_signer_with_prefix_vector() {
+        let prefix = b"\x19Custom Signed Message:\n";
+        let signature = vector_signature(
+            "2e9929018184f9182f3391d12d5eb933bae36cd321591db8dc37f48422e9e7c9\
+             3f4b240aff535ed1005076978931cfa779562cb8da4ad386b0a7b89df4f84dbc1c",
+        );
+
+        assert_eq!(
+            SignatureRecover::recover_signer_with_prefix(prefix, "hello", &signature),
+            Ok(VECTOR_SIGNER)
+        );
+
+        // The same signature under the EIP-191 prefix is not the signer's
+        assert_ne!(SignatureRecover::recover_signer("hello", &signature), Ok(VECTOR_SIGNER));
+    }
+
+    #[test]
+    fn test_verify_with_deadline_vector() {
+        // personal_sign("1,orderCoffee,Espresso,2,100,42,1700000000")
+        let signature = vector_signature(
+            "9db1393aea08470e2ec716e6210afe314497b5dfe5244569ee0dd2cce51cff62\
+             60ceb48feb9688d255eb0cb24089edc504ea2d3a9d887839065ffb2c27c9ff891b",
+        );
+        let deadline = U256::from(1_700_000_000u64);
+        let verify = |deadline: U256, now: U256, signer: Address| {
+            SignatureRecover::verify_with_deadline(
+                "1",
+                "orderCoffee",
+                "Espresso,2,100,42",
+                deadline,
+                now,
+                &signature,
+                signer,
+            )
+        };
+
+        assert_eq!(verify(deadline, deadline - U256::from(1), VECTOR_SIGNER), Ok(true));
+        assert_eq!(verify(deadline, deadline, VECTOR_SIGNER), Ok(true));
+        assert_eq!(verify(deadline, deadline - U256::from(1), Address::ZERO), Ok(false));
+
+        // The deadline is part of the signed message, so extending it fails
+        assert_eq!(verify(deadline + U256::from(1), deadline, VECTOR_SIGNER), Ok(false));
+        assert_eq!(
+            verify(deadline, deadline + U256::from(1), VECTOR_SIGNER),
+            Err(SignatureError::Expired)
+        );
+    }
+
+    #[test]
+    fn test_ecrecover_input_layout() {
+        let hash = B256::repeat_byte(0x11);
+        let r = B256::repeat_byte(0x22);
+        let s = B256::repeat_byte(0x33);
+
+        let input = SignatureRecover::ecrecover_input(&hash, 28, &r, &s);
+        assert_eq!(input[..32], hash[..]);
+        assert!(input[32..63].iter().all(|b| *b == 0));
+        assert_eq!(input[63], 28);
+        assert_eq!(input[64..96], r[..]);
+        assert_eq!(input[96..], s[..]);
+    }
+
+    #[test]
+    fn test_recover_signer_with_prefix_invalid_length() {
+        let result = SignatureRecover::recover_signer_with_prefix(b"prefix", "hello", &[0u8; 10]);
+        assert!(matches!(result, Err(SignatureError::InvalidLength)));
+    }
+
+    #[test]
+    fn test_recover_from_hash_invalid_length() {
+        let result = SignatureRecover::recover_from_hash(&B256::ZERO, &[0u8; 66]);
+        assert!(matches!(result, Err(SignatureError::InvalidLength)));
+    }
 }
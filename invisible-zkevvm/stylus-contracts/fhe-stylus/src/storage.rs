@@ -0,0 +1,85 @@
+//! Storage Helpers for Encrypted Values
+//!
+//! # Why `handle == ZERO` Is Not an Emptiness Test
+//! A `StorageMap<Address, StorageB256>` returns the zero handle for keys that
+//! were never written. Handles are opaque references into the coprocessor, so
+//! the zero handle carries no meaning of its own: it is not an encryption of
+//! zero, and nothing stops a contract from storing it on purpose. Treating it
+//! as "unset" led to the silent-zero bug in EVVMCafhe's removed balance getters.
+//!
+//! [`EncryptedSlot`] keeps an explicit existence flag next to each handle, so
+//! an unset key reads as `None`.
+
+use stylus_sdk::alloy_primitives::{Address, B256};
+use stylus_sdk::prelude::*;
+use stylus_sdk::storage::{StorageB256, StorageBool, StorageMap};
+
+use crate::types::Euint64;
+
+/// Encrypted handles keyed by address, with an existence flag per key
+///
+/// # Example
+/// ```ignore
+/// #[storage]
+/// #[entrypoint]
+/// pub struct MyContract {
+///     balances: EncryptedSlot,
+/// }
+///
+/// // Unset balances are an error, not a zero handle
+/// let balance = self.balances.get_checked(user).ok_or(b"No balance".to_vec())?;
+/// ```
+#[storage]
+pub struct EncryptedSlot {
+    handles: StorageMap<Address, StorageB256>,
+    is_set: StorageMap<Address, StorageBool>,
+}
+
+impl EncryptedSlot {
+    /// Get the handle stored for `key`, or `None` if it was never set
+    pub fn get_checked(&self, key: Address) -> Option<Euint64> {
+        checked_handle(self.is_set.get(key), self.handles.get(key))
+    }
+
+    /// Whether a handle has been stored for `key`
+    pub fn contains(&self, key: Address) -> bool {
+        self.is_set.get(key)
+    }
+
+    /// Store a handle for `key`
+    pub fn set(&mut self, key: Address, handle: Euint64) {
+        self.handles.insert(key, handle);
+        self.is_set.insert(key, true);
+    }
+
+    /// Remove the handle for `key`, so it reads as unset again
+    pub fn clear(&mut self, key: Address) {
+        self.handles.delete(key);
+        self.is_set.delete(key);
+    }
+}
+
+/// Combine an existence flag and a raw stored handle
+fn checked_handle(is_set: bool, handle: B256) -> Option<Euint64> {
+    is_set.then_some(handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_handle_unset() {
+        assert_eq!(checked_handle(false, B256::ZERO), None);
+    }
+
+    #[test]
+    fn test_checked_handle_set() {
+        // A stored zero handle is still a value
+        assert_eq!(checked_handle(true, B256::ZERO), Some(B256::ZERO));
+        assert_eq!(
+            checked_handle(true, B256::repeat_byte(0x11)),
+            Some(B256::repeat_byte(0x11))
+        );
+    }
+}
@@ -6,6 +6,35 @@
 
 use stylus_sdk::alloy_primitives::FixedBytes;
 
+use crate::interfaces::{
+    EADDRESS_TYPE, EBOOL_TYPE, EUINT128_TYPE, EUINT16_TYPE, EUINT256_TYPE, EUINT32_TYPE,
+    EUINT64_TYPE, EUINT8_TYPE,
+};
+
+/// Encrypted 8-bit unsigned integer
+///
+/// Equivalent to Solidity's `euint8` type. Handy for small counters.
+pub type Euint8 = FixedBytes<32>;
+
+/// External encrypted 8-bit unsigned integer
+pub type ExternalEuint8 = FixedBytes<32>;
+
+/// Encrypted 16-bit unsigned integer
+///
+/// Equivalent to Solidity's `euint16` type.
+pub type Euint16 = FixedBytes<32>;
+
+/// External encrypted 16-bit unsigned integer
+pub type ExternalEuint16 = FixedBytes<32>;
+
+/// Encrypted 32-bit unsigned integer
+///
+/// Equivalent to Solidity's `euint32` type.
+pub type Euint32 = FixedBytes<32>;
+
+/// External encrypted 32-bit unsigned integer
+pub type ExternalEuint32 = FixedBytes<32>;
+
 /// Encrypted 64-bit unsigned integer (internal representation)
 ///
 /// Wraps a 32-byte handle that references an encrypted value in the FHEVM system.
@@ -23,6 +52,14 @@ pub type ExternalEuint64 = FixedBytes<32>;
 /// Equivalent to Solidity's `ebool` type.
 pub type Ebool = FixedBytes<32>;
 
+/// Encrypted 128-bit unsigned integer
+///
+/// Equivalent to Solidity's `euint128` type.
+pub type Euint128 = FixedBytes<32>;
+
+/// External encrypted 128-bit unsigned integer
+pub type ExternalEuint128 = FixedBytes<32>;
+
 /// Encrypted 256-bit unsigned integer
 ///
 /// Equivalent to Solidity's `euint256` type.
@@ -31,12 +68,166 @@ pub type Euint256 = FixedBytes<32>;
 /// External encrypted 256-bit unsigned integer
 pub type ExternalEuint256 = FixedBytes<32>;
 
+/// Kind of encrypted value, independent of any backend's numbering
+///
+/// Zama FHEVM and Fhenix CoFHE number their encrypted types differently
+/// (e.g. a 64-bit integer is `3` for the Zama input verifier but `5` in
+/// CoFHE's `Utils.EUINT64_TFHE`). Passing one scheme's byte to the other
+/// silently produces a ciphertext of the wrong type, so convert through this
+/// enum instead of using raw `u8` constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptedType {
+    Bool,
+    Uint8,
+    Uint16,
+    Uint32,
+    Uint64,
+    Uint128,
+    Uint256,
+    Address,
+}
+
+impl EncryptedType {
+    /// Type byte expected by the Zama input verifier (`interfaces::EUINT*_TYPE`)
+    pub const fn as_zama_u8(self) -> u8 {
+        match self {
+            Self::Uint8 => EUINT8_TYPE,
+            Self::Uint16 => EUINT16_TYPE,
+            Self::Uint32 => EUINT32_TYPE,
+            Self::Uint64 => EUINT64_TYPE,
+            Self::Uint128 => EUINT128_TYPE,
+            Self::Uint256 => EUINT256_TYPE,
+            Self::Bool => EBOOL_TYPE,
+            Self::Address => EADDRESS_TYPE,
+        }
+    }
+
+    /// Type byte used by Fhenix CoFHE (`Utils.*_TFHE` in cofhe-contracts)
+    pub const fn as_cofhe_u8(self) -> u8 {
+        match self {
+            Self::Bool => 0,
+            Self::Uint8 => 2,
+            Self::Uint16 => 3,
+            Self::Uint32 => 4,
+            Self::Uint64 => 5,
+            Self::Uint128 => 6,
+            Self::Address => 7,
+            Self::Uint256 => 8,
+        }
+    }
+
+    /// Parse a Fhenix CoFHE `utype` byte
+    pub const fn from_cofhe_u8(utype: u8) -> Option<Self> {
+        match utype {
+            0 => Some(Self::Bool),
+            2 => Some(Self::Uint8),
+            3 => Some(Self::Uint16),
+            4 => Some(Self::Uint32),
+            5 => Some(Self::Uint64),
+            6 => Some(Self::Uint128),
+            7 => Some(Self::Address),
+            8 => Some(Self::Uint256),
+            _ => None,
+        }
+    }
+
+    /// Number of plaintext bits: 1 for bool, 160 for address
+    ///
+    /// To size a raw type byte, parse it first:
+    /// `EncryptedType::from_cofhe_u8(utype).map(EncryptedType::bit_width)`.
+    pub const fn bit_width(self) -> u32 {
+        match self {
+            Self::Bool => 1,
+            Self::Uint8 => 8,
+            Self::Uint16 => 16,
+            Self::Uint32 => 32,
+            Self::Uint64 => 64,
+            Self::Uint128 => 128,
+            Self::Uint256 => 256,
+            Self::Address => 160,
+        }
+    }
+
+    /// Whether this is an unsigned integer type (not bool or address)
+    pub const fn is_integer(self) -> bool {
+        !matches!(self, Self::Bool | Self::Address)
+    }
+
+    /// Parse a Zama input verifier type byte
+    pub const fn from_zama_u8(input_type: u8) -> Option<Self> {
+        match input_type {
+            EUINT8_TYPE => Some(Self::Uint8),
+            EUINT16_TYPE => Some(Self::Uint16),
+            EUINT32_TYPE => Some(Self::Uint32),
+            EUINT64_TYPE => Some(Self::Uint64),
+            EUINT128_TYPE => Some(Self::Uint128),
+            EUINT256_TYPE => Some(Self::Uint256),
+            EBOOL_TYPE => Some(Self::Bool),
+            EADDRESS_TYPE => Some(Self::Address),
+            _ => None,
+        }
+    }
+}
+
 // Since these are just type aliases for FixedBytes<32>, they automatically
 // inherit all the necessary implementations including:
 // - AbiType, AbiEncode, AbiDecode (for contract ABI)
 // - Debug, Clone, Copy, PartialEq, Eq, and other common traits
 // - Conversion to/from bytes
 
+/// Length of a `0x`-prefixed hex handle: 2 + 32 * 2
+pub const HANDLE_HEX_LEN: usize = 66;
+
+/// Fixed-size, stack-allocated hex rendering of a handle
+///
+/// Returned by [`handle_to_hex`]; no heap allocation is involved.
+#[derive(Clone, Copy)]
+pub struct HandleHex([u8; HANDLE_HEX_LEN]);
+
+impl HandleHex {
+    /// The `0x`-prefixed lowercase hex string
+    pub fn as_str(&self) -> &str {
+        // Only ASCII hex digits are ever written to the buffer
+        core::str::from_utf8(&self.0).unwrap_or_default()
+    }
+
+    /// The raw ASCII bytes, e.g. for an event or revert payload
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl core::fmt::Display for HandleHex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::fmt::Debug for HandleHex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Format an encrypted handle as `0x`-prefixed lowercase hex for debug logging
+///
+/// Works for any handle type, since they all share the `FixedBytes<32>`
+/// representation. Uses a fixed 66-byte buffer rather than `String` to keep
+/// code size down.
+pub fn handle_to_hex(handle: &Euint64) -> HandleHex {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut buf = [0u8; HANDLE_HEX_LEN];
+    buf[0] = b'0';
+    buf[1] = b'x';
+    for (i, byte) in handle.iter().enumerate() {
+        buf[2 + i * 2] = HEX_DIGITS[(byte >> 4) as usize];
+        buf[3 + i * 2] = HEX_DIGITS[(byte & 0x0f) as usize];
+    }
+
+    HandleHex(buf)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,4 +248,83 @@ mod tests {
         // Both should be equal since they're the same underlying type
         assert_eq!(euint.as_slice(), external.as_slice());
     }
+
+    #[test]
+    fn test_encrypted_type_encodings() {
+        // (variant, zama byte, cofhe byte), with the Zama bytes pinned per FHEVM version
+        #[cfg(not(feature = "fhevm-v0-7"))]
+        let table = [
+            (EncryptedType::Bool, 6, 0),
+            (EncryptedType::Uint8, 0, 2),
+            (EncryptedType::Uint16, 1, 3),
+            (EncryptedType::Uint32, 2, 4),
+            (EncryptedType::Uint64, 3, 5),
+            (EncryptedType::Uint128, 4, 6),
+            (EncryptedType::Uint256, 5, 8),
+            (EncryptedType::Address, 7, 7),
+        ];
+        #[cfg(feature = "fhevm-v0-7")]
+        let table = [
+            (EncryptedType::Bool, 0, 0),
+            (EncryptedType::Uint8, 2, 2),
+            (EncryptedType::Uint16, 3, 3),
+            (EncryptedType::Uint32, 4, 4),
+            (EncryptedType::Uint64, 5, 5),
+            (EncryptedType::Uint128, 6, 6),
+            (EncryptedType::Uint256, 8, 8),
+            (EncryptedType::Address, 7, 7),
+        ];
+
+        for (ty, zama, cofhe) in table {
+            assert_eq!(ty.as_zama_u8(), zama);
+            assert_eq!(ty.as_cofhe_u8(), cofhe);
+            assert_eq!(EncryptedType::from_zama_u8(zama), Some(ty));
+            assert_eq!(EncryptedType::from_cofhe_u8(cofhe), Some(ty));
+        }
+        assert_eq!(EncryptedType::from_zama_u8(42), None);
+        assert_eq!(EncryptedType::from_cofhe_u8(1), None);
+    }
+
+    #[test]
+    fn test_bit_width() {
+        let widths = [(0, 1), (2, 8), (3, 16), (4, 32), (5, 64), (6, 128), (7, 160), (8, 256)];
+        for (utype, bits) in widths {
+            assert_eq!(EncryptedType::from_cofhe_u8(utype).map(EncryptedType::bit_width), Some(bits));
+        }
+        assert_eq!(EncryptedType::from_cofhe_u8(42).map(EncryptedType::bit_width), None);
+    }
+
+    #[test]
+    fn test_is_integer() {
+        assert!(EncryptedType::Uint8.is_integer());
+        assert!(EncryptedType::Uint256.is_integer());
+        assert!(!EncryptedType::Bool.is_integer());
+        assert!(!EncryptedType::Address.is_integer());
+    }
+
+    #[test]
+    fn test_handle_to_hex_zero() {
+        let hex = handle_to_hex(&Euint64::ZERO);
+        assert_eq!(
+            hex.as_str(),
+            "0x0000000000000000000000000000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_handle_to_hex_full_bytes() {
+        let hex = handle_to_hex(&Euint64::repeat_byte(0xff));
+        assert_eq!(
+            hex.as_str(),
+            "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0xab;
+        bytes[31] = 0x09;
+        let hex = handle_to_hex(&FixedBytes(bytes));
+        assert!(hex.as_str().starts_with("0xab00"));
+        assert!(hex.as_str().ends_with("0009"));
+        assert_eq!(hex.as_bytes().len(), HANDLE_HEX_LEN);
+    }
 }
@@ -0,0 +1,88 @@
+//! Versioned Zama Type Bytes
+//!
+//! Zama has renumbered its encrypted types between FHEVM releases. A
+//! contract built against the wrong numbering creates mis-typed ciphertexts
+//! without any error, so each numbering lives in its own module here and one
+//! is selected at compile time:
+//!
+//! - [`legacy`] - the numbering this crate has always used (default)
+//! - [`v0_7`] - FHEVM v0.7 `FheType` order, enabled with the `fhevm-v0-7` feature
+//!
+//! The selected module is re-exported as [`selected`] and through the
+//! `EUINT*_TYPE` constants in [`crate::interfaces`], which every `FHE`
+//! wrapper and [`EncryptedType::as_zama_u8`](crate::types::EncryptedType::as_zama_u8) use.
+
+/// Type bytes used by this crate before versioning was introduced
+///
+/// Integer types in width order starting at 0, then `ebool` and `eaddress`.
+pub mod legacy {
+    pub const EUINT8_TYPE: u8 = 0;
+    pub const EUINT16_TYPE: u8 = 1;
+    pub const EUINT32_TYPE: u8 = 2;
+    pub const EUINT64_TYPE: u8 = 3;
+    pub const EUINT128_TYPE: u8 = 4;
+    pub const EUINT256_TYPE: u8 = 5;
+    pub const EBOOL_TYPE: u8 = 6;
+    pub const EADDRESS_TYPE: u8 = 7;
+}
+
+/// Type bytes of the FHEVM v0.7 `FheType` enum
+///
+/// `Bool = 0`, `Uint4 = 1` (not supported by this crate), then the integer
+/// widths, with `eaddress` as `Uint160 = 7` before `Uint256 = 8`.
+pub mod v0_7 {
+    pub const EBOOL_TYPE: u8 = 0;
+    pub const EUINT8_TYPE: u8 = 2;
+    pub const EUINT16_TYPE: u8 = 3;
+    pub const EUINT32_TYPE: u8 = 4;
+    pub const EUINT64_TYPE: u8 = 5;
+    pub const EUINT128_TYPE: u8 = 6;
+    pub const EADDRESS_TYPE: u8 = 7;
+    pub const EUINT256_TYPE: u8 = 8;
+}
+
+/// Type bytes selected by cargo features
+#[cfg(not(feature = "fhevm-v0-7"))]
+pub use legacy as selected;
+
+/// Type bytes selected by cargo features
+#[cfg(feature = "fhevm-v0-7")]
+pub use v0_7 as selected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_type_bytes() {
+        assert_eq!(legacy::EUINT8_TYPE, 0);
+        assert_eq!(legacy::EUINT16_TYPE, 1);
+        assert_eq!(legacy::EUINT32_TYPE, 2);
+        assert_eq!(legacy::EUINT64_TYPE, 3);
+        assert_eq!(legacy::EUINT128_TYPE, 4);
+        assert_eq!(legacy::EUINT256_TYPE, 5);
+        assert_eq!(legacy::EBOOL_TYPE, 6);
+        assert_eq!(legacy::EADDRESS_TYPE, 7);
+    }
+
+    #[test]
+    fn test_v0_7_type_bytes() {
+        assert_eq!(v0_7::EBOOL_TYPE, 0);
+        assert_eq!(v0_7::EUINT8_TYPE, 2);
+        assert_eq!(v0_7::EUINT16_TYPE, 3);
+        assert_eq!(v0_7::EUINT32_TYPE, 4);
+        assert_eq!(v0_7::EUINT64_TYPE, 5);
+        assert_eq!(v0_7::EUINT128_TYPE, 6);
+        assert_eq!(v0_7::EADDRESS_TYPE, 7);
+        assert_eq!(v0_7::EUINT256_TYPE, 8);
+    }
+
+    #[test]
+    fn test_selected_matches_feature() {
+        #[cfg(not(feature = "fhevm-v0-7"))]
+        assert_eq!(selected::EUINT64_TYPE, legacy::EUINT64_TYPE);
+
+        #[cfg(feature = "fhevm-v0-7")]
+        assert_eq!(selected::EUINT64_TYPE, v0_7::EUINT64_TYPE);
+    }
+}
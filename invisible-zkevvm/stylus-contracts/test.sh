@@ -2,7 +2,8 @@
 # Stylus Contract Testing Script
 #
 # This script runs the correct tests for WASM-only Stylus contracts.
-# DO NOT use `cargo test` - it won't work for no_std WASM contracts.
+# DO NOT use `cargo test` on evvm-cafhe - it won't work for no_std WASM contracts.
+# Its pure checks live in fhe-stylus, whose unit tests run natively (step 0).
 
 set -e
 
@@ -18,6 +19,11 @@ YELLOW='\033[1;33m'
 RED='\033[0;31m'
 NC='\033[0m' # No Color
 
+echo -e "${YELLOW}0. Unit Tests (fhe-stylus, native)...${NC}"
+cargo test -p fhe-stylus
+echo -e "${GREEN}✓ Unit tests passed${NC}"
+echo ""
+
 echo -e "${YELLOW}1. Type Checking...${NC}"
 cargo check --target wasm32-unknown-unknown
 echo -e "${GREEN}✓ Type check passed${NC}"
@@ -83,7 +89,8 @@ echo "╚═══════════════════════
 echo ""
 echo -e "${GREEN}✅ Contract builds successfully to WASM!${NC}"
 echo ""
-echo "Note: cargo test doesn't work for WASM-only contracts."
+echo "Note: cargo test doesn't work for the WASM-only contract;"
+echo "its checks are unit-tested in fhe-stylus (step 0)."
 echo "This script validates your contract properly."
 echo ""
 echo "Next steps:"